@@ -0,0 +1,23 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use stylua_lib::{format_code, Config, OutputVerification};
+
+pub fn format_nested_call_tables(c: &mut Criterion) {
+    c.bench_function("format nested_call_tables.lua", |b| {
+        b.iter(|| {
+            format_code(
+                black_box(include_str!("./nested_call_tables.lua")),
+                black_box(Config::default()),
+                black_box(None),
+                black_box(OutputVerification::None),
+            )
+            .map_err(Box::new)
+        })
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(40);
+    targets = format_nested_call_tables
+}
+criterion_main!(benches);
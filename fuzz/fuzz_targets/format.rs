@@ -0,0 +1,13 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use stylua_lib::{fuzz_check, Error};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(code) = std::str::from_utf8(data) {
+        match fuzz_check(code) {
+            // The input was not valid Lua, so there is nothing to check
+            Ok(()) | Err(Error::ParseError(_)) => (),
+            Err(error) => panic!("{}", error),
+        }
+    }
+});
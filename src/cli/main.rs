@@ -10,7 +10,7 @@ use std::time::Instant;
 use structopt::StructOpt;
 use threadpool::ThreadPool;
 
-use stylua_lib::{format_code, Config, OutputVerification, Range};
+use stylua_lib::{format_code, format_code_with_filename, Config, OutputVerification, Range};
 
 mod config;
 mod opt;
@@ -110,8 +110,13 @@ fn format_string(
     opt: &opt::Opt,
     verify_output: OutputVerification,
 ) -> Result<FormatResult> {
+    let filename = opt
+        .stdin_filepath
+        .as_ref()
+        .map(|path| path.display().to_string());
     let formatted_contents =
-        format_code(&input, config, range, verify_output).context("failed to format from stdin")?;
+        format_code_with_filename(&input, filename.as_deref(), config, range, verify_output)
+            .context("failed to format from stdin")?;
 
     if opt.check {
         let diff = output_diff::output_diff(
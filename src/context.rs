@@ -4,6 +4,16 @@ use full_moon::{
     tokenizer::{Token, TokenType},
 };
 
+/// A directive comment preceding a statement, which overrides how the tables and function calls within it are laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LayoutDirective {
+    /// `-- stylua: expand`: tables and function call arguments are always expanded onto multiple lines
+    Expand,
+    /// `-- stylua: collapse`: tables and function call arguments are kept on a single line where they fit,
+    /// even if they were written across multiple lines
+    Collapse,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Context {
     /// The configuration passed to the formatter
@@ -12,15 +22,33 @@ pub struct Context {
     range: Option<FormatRange>,
     /// Whether the formatting has currently been disabled. This should occur when we see the relevant comment.
     formatting_disabled: bool,
+    /// The layout directive present before the statement currently being formatted, if any.
+    layout_directive: Option<LayoutDirective>,
+    /// Whether we are currently formatting code within the body of a function.
+    in_function_body: bool,
+}
+
+/// The state of a [`Context`] other than its configuration, which stays the same for a whole formatting run.
+/// Nodes formatted with equal state and shape are formatted identically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContextState {
+    range: Option<FormatRange>,
+    formatting_disabled: bool,
+    layout_directive: Option<LayoutDirective>,
+    in_function_body: bool,
 }
 
 impl Context {
     /// Creates a new Context, with the given configuration
     pub fn new(config: Config, range: Option<FormatRange>) -> Self {
+        let config = crate::effective_config(config);
+
         Self {
             config,
             range,
             formatting_disabled: false,
+            layout_directive: None,
+            in_function_body: false,
         }
     }
 
@@ -63,6 +91,69 @@ impl Context {
         *self
     }
 
+    /// Determines the layout directive for the given statement, from a `-- stylua: expand` or `-- stylua: collapse`
+    /// comment in its leading trivia. If both are present, the last one takes precedence.
+    /// This should be called for every statement, so that a directive does not carry over onto the next statement.
+    pub fn check_layout_directive(&self, node: &impl Node) -> Self {
+        let mut layout_directive = None;
+
+        let leading_trivia = node.surrounding_trivia().0;
+        for trivia in leading_trivia {
+            let comment_lines = match trivia.token_type() {
+                TokenType::SingleLineComment { comment } => comment,
+                TokenType::MultiLineComment { comment, .. } => comment,
+                _ => continue,
+            }
+            .lines()
+            .map(|line| line.trim());
+
+            for line in comment_lines {
+                match line {
+                    "stylua: expand" => layout_directive = Some(LayoutDirective::Expand),
+                    "stylua: collapse" => layout_directive = Some(LayoutDirective::Collapse),
+                    _ => (),
+                }
+            }
+        }
+
+        Self {
+            layout_directive,
+            ..*self
+        }
+    }
+
+    /// The layout directive which applies to the statement currently being formatted, if any
+    pub fn layout_directive(&self) -> Option<LayoutDirective> {
+        self.layout_directive
+    }
+
+    /// The state of this context other than its configuration, which may change how a node is formatted
+    pub fn state(&self) -> ContextState {
+        ContextState {
+            range: self.range,
+            formatting_disabled: self.formatting_disabled,
+            layout_directive: self.layout_directive,
+            in_function_body: self.in_function_body,
+        }
+    }
+
+    /// Returns a new Context for formatting the body of a function
+    pub fn enter_function_body(&self) -> Self {
+        Self {
+            in_function_body: true,
+            ..*self
+        }
+    }
+
+    /// The maximum number of consecutive blank lines to keep, which depends on whether we are within a function body
+    pub fn max_blank_lines(&self) -> usize {
+        if self.in_function_body {
+            self.config.max_blank_lines_in_function
+        } else {
+            1
+        }
+    }
+
     /// Checks whether we should format the given node.
     /// Firstly determine if formatting is disabled (due to the relevant comment)
     /// If not, determine whether the node has an ignore comment present.
@@ -129,7 +220,7 @@ macro_rules! check_should_format {
 }
 
 /// Returns the relevant line ending string from the [`LineEndings`] enum
-fn line_ending_character(line_endings: LineEndings) -> String {
+pub fn line_ending_character(line_endings: LineEndings) -> String {
     match line_endings {
         LineEndings::Unix => String::from("\n"),
         LineEndings::Windows => String::from("\r\n"),
@@ -96,8 +96,9 @@ fn hang_equal_token(
 }
 
 /// Attempts different formatting tactics on an expression list being assigned (`= foo, bar`), to find the best
-/// formatting output.
-fn attempt_assignment_tactics(
+/// formatting output. This is also used for the right hand side of compound assignments (`+= foo`), where the
+/// compound operator's token is passed as the equal token.
+pub fn attempt_assignment_tactics(
     ctx: &Context,
     expressions: &Punctuated<Expression>,
     shape: Shape,
@@ -264,14 +265,48 @@ pub fn format_assignment(ctx: &Context, assignment: &Assignment, shape: Shape) -
             + 3
             + strip_trailing_trivia(&expr_list).to_string().len());
     if contains_comments || singleline_shape.over_budget() {
-        // We won't attempt anything else with the var_list. Format it normally
-        var_list = try_format_punctuated(ctx, assignment.variables(), shape, format_var, Some(1));
-        let shape = shape + (strip_leading_trivia(&var_list).to_string().len() + 3);
-
-        let (new_expr_list, new_equal_token) =
-            attempt_assignment_tactics(ctx, assignment.expressions(), shape, equal_token);
-        expr_list = new_expr_list;
-        equal_token = new_equal_token;
+        // Check whether the var list itself fits on the line, i.e. `a, b, c =`
+        let vars_shape = shape + (strip_leading_trivia(&var_list).to_string().len() + 2); // 2 = " ="
+
+        let hang_vars = vars_shape.over_budget()
+            && assignment.variables().len() > 1
+            && !trivia_util::contains_comments(assignment.variables());
+
+        let shape = if hang_vars {
+            // Hang the vars one per line, and continue the assignment from the last var
+            var_list = format_punctuated_multiline(
+                ctx,
+                assignment.variables(),
+                shape,
+                format_var,
+                Some(1),
+            );
+
+            let last_var = strip_trivia(var_list.iter().last().unwrap());
+            shape
+                .with_indent(shape.indent().add_indent_level(1))
+                .reset()
+                .take_last_line(&last_var)
+                + 3 // 3 = " = "
+        } else {
+            // We won't attempt anything else with the var_list. Format it normally
+            var_list =
+                try_format_punctuated(ctx, assignment.variables(), shape, format_var, Some(1));
+            shape + (strip_leading_trivia(&var_list).to_string().len() + 3)
+        };
+
+        // If we hung the vars, the expressions may now fit after the last var without any further changes
+        if hang_vars
+            && !contains_comments
+            && !(shape + strip_trailing_trivia(&expr_list).to_string().len()).over_budget()
+        {
+            expr_list = format_punctuated(ctx, assignment.expressions(), shape, format_expression);
+        } else {
+            let (new_expr_list, new_equal_token) =
+                attempt_assignment_tactics(ctx, assignment.expressions(), shape, equal_token);
+            expr_list = new_expr_list;
+            equal_token = new_equal_token;
+        }
     }
 
     // Add necessary trivia
@@ -401,21 +436,67 @@ pub fn format_local_assignment(
                 + strip_trailing_trivia(&expr_list).to_string().len());
 
         if contains_comments || singleline_shape.over_budget() {
-            // We won't attempt anything else with the name_list. Format it normally
-            name_list = try_format_punctuated(
-                ctx,
-                assignment.names(),
-                shape,
-                format_token_reference,
-                Some(1),
-            );
-            let shape = shape
-                + (strip_leading_trivia(&name_list).to_string().len() + 6 + 3 + type_specifier_len);
-
-            let (new_expr_list, new_equal_token) =
-                attempt_assignment_tactics(ctx, assignment.expressions(), shape, equal_token);
-            expr_list = new_expr_list;
-            equal_token = new_equal_token;
+            // Check whether the name list itself fits on the line, i.e. `local a, b, c =`
+            let names_shape = shape
+                + (strip_leading_trivia(&name_list).to_string().len() + 6 + 2 + type_specifier_len); // 6 = "local ", 2 = " ="
+
+            let hang_names = names_shape.over_budget()
+                && assignment.names().len() > 1
+                && !trivia_util::contains_comments(assignment.names());
+
+            let shape = if hang_names {
+                // Hang the names one per line, and continue the assignment from the last name
+                name_list = format_punctuated_multiline(
+                    ctx,
+                    assignment.names(),
+                    shape + 6, // 6 = "local "
+                    format_token_reference,
+                    Some(1),
+                );
+
+                let last_name = name_list.iter().last().unwrap();
+                #[cfg(feature = "luau")]
+                let last_name_len = strip_trivia(last_name).to_string().len()
+                    + type_specifiers
+                        .last()
+                        .and_then(|x| x.as_ref())
+                        .map_or(0, |y| y.to_string().len());
+                #[cfg(not(feature = "luau"))]
+                let last_name_len = strip_trivia(last_name).to_string().len();
+
+                shape
+                    .with_indent(shape.indent().add_indent_level(1))
+                    .reset()
+                    + (last_name_len + 3) // 3 = " = "
+            } else {
+                // We won't attempt anything else with the name_list. Format it normally
+                name_list = try_format_punctuated(
+                    ctx,
+                    assignment.names(),
+                    shape,
+                    format_token_reference,
+                    Some(1),
+                );
+                shape
+                    + (strip_leading_trivia(&name_list).to_string().len()
+                        + 6
+                        + 3
+                        + type_specifier_len)
+            };
+
+            // If we hung the names, the expressions may now fit after the last name without any further changes
+            if hang_names
+                && !contains_comments
+                && !(shape + strip_trailing_trivia(&expr_list).to_string().len()).over_budget()
+            {
+                expr_list =
+                    format_punctuated(ctx, assignment.expressions(), shape, format_expression);
+            } else {
+                let (new_expr_list, new_equal_token) =
+                    attempt_assignment_tactics(ctx, assignment.expressions(), shape, equal_token);
+                expr_list = new_expr_list;
+                equal_token = new_equal_token;
+            }
         }
 
         // Add necessary trivia
@@ -5,6 +5,7 @@ use crate::{
         assignment::hang_punctuated_list,
         expression::{format_expression, hang_expression},
         general::{format_punctuated, format_punctuated_multiline, format_symbol},
+        should_abort,
         stmt::format_stmt,
         trivia::{
             strip_trailing_trivia, strip_trivia, FormatTriviaType, UpdateLeadingTrivia,
@@ -15,8 +16,10 @@ use crate::{
     shape::Shape,
 };
 use full_moon::ast::{
-    punctuated::Punctuated, Block, Expression, LastStmt, Prefix, Return, Stmt, Value, Var,
+    punctuated::Punctuated, Block, Call, Expression, LastStmt, Prefix, Return, Stmt, Suffix, Value,
+    Var,
 };
+use full_moon::node::Node;
 use full_moon::tokenizer::TokenType;
 use full_moon::tokenizer::{Token, TokenReference};
 
@@ -174,6 +177,7 @@ fn format_last_stmt_block(ctx: &Context, last_stmt: &LastStmt, shape: Shape) ->
 
             LastStmt::Return(return_node.to_owned().with_returns(returns))
         }
+        // `break` and `continue` do not contain any blocks, so are left untouched
         other => other.to_owned(),
     }
 }
@@ -403,17 +407,327 @@ fn last_stmt_remove_leading_newlines(last_stmt: LastStmt) -> LastStmt {
     }
 }
 
+/// Information about a single line statement which ends with a trailing comment, used to align trailing comments
+struct TrailingComment {
+    /// The width of the line up to the end of the code, before the comment
+    code_width: usize,
+    /// The width of the comment, including the `--`
+    comment_width: usize,
+    /// Whether the statement is separated from the previous statement by a blank line or a comment
+    separated: bool,
+}
+
+/// Returns whether a statement ends with an expression, which would be continued by a following `(`, e.g. `a = b (c)()`.
+/// A local assignment without any expressions is included, as a following `(` would not be parsed as a new statement.
+fn stmt_ends_with_expression(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Assignment(_)
+        | Stmt::LocalAssignment(_)
+        | Stmt::FunctionCall(_)
+        | Stmt::Repeat(_) => true,
+        #[cfg(feature = "luau")]
+        Stmt::CompoundAssignment(_) => true,
+        _ => false,
+    }
+}
+
+/// Returns whether a statement starts with a parenthesised expression, such as `(foo or bar)()` or `(foo).bar = baz`.
+/// If placed after a statement ending with an expression, it is ambiguous whether the parentheses start a new statement.
+fn stmt_starts_with_parentheses(stmt: &Stmt) -> bool {
+    let var_starts_with_parentheses = |var: Option<&Var>| match var {
+        Some(Var::Expression(var_expression)) => matches!(
+            var_expression.prefix(),
+            Prefix::Expression(Expression::Parentheses { .. })
+        ),
+        _ => false,
+    };
+
+    match stmt {
+        Stmt::FunctionCall(function_call) => matches!(
+            function_call.prefix(),
+            Prefix::Expression(Expression::Parentheses { .. })
+        ),
+        Stmt::Assignment(assignment) => {
+            var_starts_with_parentheses(assignment.variables().iter().next())
+        }
+        #[cfg(feature = "luau")]
+        Stmt::CompoundAssignment(compound_assignment) => {
+            var_starts_with_parentheses(Some(compound_assignment.lhs()))
+        }
+        _ => false,
+    }
+}
+
+/// Returns the trailing trivia following a statement, which may have been moved onto its semicolon
+fn stmt_trailing_trivia(stmt: &Stmt, semicolon: &Option<TokenReference>) -> Vec<Token> {
+    match semicolon {
+        Some(semicolon) => semicolon.trailing_trivia().cloned().collect(),
+        None => trivia_util::get_stmt_trailing_trivia(stmt.to_owned()).1,
+    }
+}
+
+/// Finds the trailing comment of a statement, if the statement is on a single line and ends with a single comment
+fn find_trailing_comment(
+    ctx: &Context,
+    stmt: &Stmt,
+    semicolon: &Option<TokenReference>,
+) -> Option<TrailingComment> {
+    let trailing_trivia = stmt_trailing_trivia(stmt, semicolon);
+    let comment = match trailing_trivia.as_slice() {
+        [space, comment, rest @ ..]
+            if trivia_util::trivia_is_whitespace(space)
+                && !space.to_string().contains('\n')
+                && rest.iter().all(trivia_util::trivia_is_whitespace) =>
+        {
+            match comment.token_type() {
+                TokenType::SingleLineComment { .. } => comment,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    let leading_trivia: Vec<&Token> = stmt.tokens().next()?.leading_trivia().collect();
+    let separated = leading_trivia
+        .iter()
+        .any(|trivia| trivia_util::trivia_is_comment(trivia) || trivia.to_string().contains('\n'));
+    let leading_width: usize = leading_trivia
+        .iter()
+        .map(|trivia| trivia.to_string().len())
+        .sum();
+
+    let mut code = match semicolon {
+        Some(_) => stmt.to_string(),
+        None => trivia_util::get_stmt_trailing_trivia(stmt.to_owned())
+            .0
+            .to_string(),
+    };
+    if let Some(semicolon) = semicolon {
+        code.push_str(&semicolon.token().to_string());
+    }
+
+    // Only align statements which are on a single line
+    if code[leading_width..].contains('\n') {
+        return None;
+    }
+
+    let code_line = code.rsplit('\n').next().unwrap_or_default();
+    let code_width = code_line
+        .chars()
+        .map(|c| {
+            if c == '\t' {
+                ctx.config().indent_width
+            } else {
+                1
+            }
+        })
+        .sum();
+
+    Some(TrailingComment {
+        code_width,
+        comment_width: comment.to_string().chars().count(),
+        separated,
+    })
+}
+
+/// Aligns the trailing comments of consecutive single line statements to a common column.
+/// Groups of statements are broken up by a blank line, a comment, or a statement without a trailing comment.
+/// If aligning a group would place a comment over the column width, the group is left unaligned.
+fn align_trailing_comments(
+    ctx: &Context,
+    statements: Vec<(Stmt, Option<TokenReference>)>,
+    formatted_flags: &[bool],
+) -> Vec<(Stmt, Option<TokenReference>)> {
+    let trailing_comments: Vec<Option<TrailingComment>> = statements
+        .iter()
+        .zip(formatted_flags)
+        .map(|((stmt, semicolon), formatted)| match formatted {
+            true => find_trailing_comment(ctx, stmt, semicolon),
+            false => None,
+        })
+        .collect();
+
+    // Split the statements into groups of consecutive statements with trailing comments, and find the column to align to
+    let mut alignments: Vec<Option<usize>> = vec![None; statements.len()];
+    let mut index = 0;
+    while index < statements.len() {
+        if trailing_comments[index].is_none() {
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        index += 1;
+        while let Some(Some(comment)) = trailing_comments.get(index) {
+            if comment.separated {
+                break;
+            }
+            index += 1;
+        }
+
+        let group = &trailing_comments[start..index];
+        if group.len() < 2 {
+            continue;
+        }
+
+        let column = group
+            .iter()
+            .flatten()
+            .map(|comment| comment.code_width)
+            .max()
+            .unwrap_or(0);
+        let fits = group
+            .iter()
+            .flatten()
+            .all(|comment| column + 1 + comment.comment_width <= ctx.config().column_width);
+
+        if fits {
+            for alignment in &mut alignments[start..index] {
+                *alignment = Some(column);
+            }
+        }
+    }
+
+    statements
+        .into_iter()
+        .zip(trailing_comments)
+        .zip(alignments)
+        .map(|(((stmt, semicolon), comment), alignment)| {
+            let (column, comment) = match (alignment, comment) {
+                (Some(column), Some(comment)) => (column, comment),
+                _ => return (stmt, semicolon),
+            };
+
+            let mut trailing_trivia = stmt_trailing_trivia(&stmt, &semicolon);
+            trailing_trivia[0] = Token::new(TokenType::spaces(column - comment.code_width + 1));
+
+            match semicolon {
+                Some(semicolon) => (
+                    stmt,
+                    Some(
+                        semicolon
+                            .update_trailing_trivia(FormatTriviaType::Replace(trailing_trivia)),
+                    ),
+                ),
+                None => {
+                    // Move the trailing trivia onto an empty token in place of a semicolon, in the same way as when
+                    // a semicolon is removed
+                    let (stmt, _) = trivia_util::get_stmt_trailing_trivia(stmt);
+                    let token = TokenReference::new(
+                        vec![],
+                        Token::new(TokenType::spaces(0)),
+                        trailing_trivia,
+                    );
+                    (stmt, Some(token))
+                }
+            }
+        })
+        .collect()
+}
+
+/// Whether the statement is a `local x = require(...)` statement, where every expression is a call to `require`
+fn is_require(stmt: &Stmt) -> bool {
+    // The call may be indexed into, e.g. `require(x).y`
+    let is_require_call = |expression: &Expression| {
+        let (prefix, first_suffix) = match expression {
+            Expression::Value { value, .. } => match &**value {
+                Value::FunctionCall(function_call) => {
+                    (function_call.prefix(), function_call.suffixes().next())
+                }
+                Value::Var(Var::Expression(var_expression)) => {
+                    (var_expression.prefix(), var_expression.suffixes().next())
+                }
+                _ => return false,
+            },
+            _ => return false,
+        };
+
+        matches!(prefix, Prefix::Name(name) if name.token().to_string() == "require")
+            && matches!(first_suffix, Some(Suffix::Call(Call::AnonymousCall(_))))
+    };
+
+    match stmt {
+        Stmt::LocalAssignment(local_assignment) => {
+            !local_assignment.expressions().is_empty()
+                && local_assignment.expressions().iter().all(is_require_call)
+        }
+        _ => false,
+    }
+}
+
+/// Ensures that a single blank line separates the run of require statements at the start of a block from the code
+/// following it. The blank line is added onto the trailing trivia of the last require statement, unless the following
+/// statement already starts with one. `next_last_stmt` is the formatted last statement of the block, if present,
+/// and whether it was formatted.
+fn separate_requires(
+    ctx: &Context,
+    block: &Block,
+    formatted_statements: &mut [(Stmt, Option<TokenReference>)],
+    formatted_flags: &[bool],
+    next_last_stmt: Option<(&LastStmt, bool)>,
+) {
+    let require_count = block.stmts().take_while(|stmt| is_require(stmt)).count();
+    if require_count == 0 || !formatted_flags[require_count - 1] {
+        return;
+    }
+
+    let next_leading_trivia = match formatted_statements.get(require_count) {
+        Some((stmt, _)) if formatted_flags[require_count] => stmt.surrounding_trivia().0,
+        None => match next_last_stmt {
+            Some((last_stmt, true)) => last_stmt.surrounding_trivia().0,
+            _ => return,
+        },
+        _ => return,
+    };
+    let next_starts_with_blank_line = matches!(
+        next_leading_trivia.first(),
+        Some(trivia) if trivia_util::trivia_is_newline(trivia)
+    );
+    if next_starts_with_blank_line {
+        return;
+    }
+
+    let (stmt, semicolon) = &mut formatted_statements[require_count - 1];
+    let blank_line = create_newline_trivia(ctx);
+    *semicolon = Some(match semicolon.take() {
+        Some(semicolon) => {
+            semicolon.update_trailing_trivia(FormatTriviaType::Append(vec![blank_line]))
+        }
+        None => {
+            // Move the trailing trivia onto an empty token in place of a semicolon, in the same way as when
+            // a semicolon is removed
+            let (updated_stmt, mut trailing_trivia) =
+                trivia_util::get_stmt_trailing_trivia(stmt.to_owned());
+            *stmt = updated_stmt;
+            trailing_trivia.push(blank_line);
+            TokenReference::new(vec![], Token::new(TokenType::spaces(0)), trailing_trivia)
+        }
+    });
+}
+
 /// Formats a block node. Note: the given shape to the block formatter should already be at the correct indentation level
 pub fn format_block(ctx: &Context, block: &Block, shape: Shape) -> Block {
     let mut ctx = *ctx;
     let mut formatted_statements: Vec<(Stmt, Option<TokenReference>)> = Vec::new();
     let mut found_first_stmt = false;
     let mut stmt_iterator = block.stmts_with_semicolon().peekable();
+    // Whether each statement was formatted, or left as-is due to an ignore comment or range
+    let mut formatted_flags = Vec::new();
 
     while let Some((stmt, semi)) = stmt_iterator.next() {
-        ctx = ctx.check_toggle_formatting(stmt);
+        // If formatting has been aborted, stop as soon as possible. The output will be discarded
+        if should_abort() {
+            return block.to_owned();
+        }
+
+        ctx = ctx
+            .check_toggle_formatting(stmt)
+            .check_layout_directive(stmt);
+        formatted_flags.push(ctx.should_format_node(stmt));
 
         let shape = shape.reset();
+        // Every formatted statement ends with a newline, so statements are always placed one per line,
+        // regardless of whether they were separated by whitespace or a semicolon in the input
         let mut stmt = format_stmt(&ctx, stmt, shape);
 
         // If this is the first stmt, then remove any leading newlines
@@ -424,35 +738,14 @@ pub fn format_block(ctx: &Context, block: &Block, shape: Shape) -> Block {
             found_first_stmt = true;
         }
 
-        // Need to check next statement if it is a function call, with a parameters expression as the prefix
-        // If so, removing a semicolon may lead to ambiguous syntax
-        // Ambiguous syntax can only occur if the current statement is a (Local)Assignment, FunctionCall or a Repeat block
-        let require_semicolon = match stmt {
-            Stmt::Assignment(_)
-            | Stmt::LocalAssignment(_)
-            | Stmt::FunctionCall(_)
-            | Stmt::Repeat(_) => {
-                let next_stmt = stmt_iterator.peek();
-                match next_stmt {
-                    Some((Stmt::FunctionCall(function_call), _)) => matches!(
-                        function_call.prefix(),
-                        Prefix::Expression(Expression::Parentheses { .. })
-                    ),
-                    Some((Stmt::Assignment(assignment), _)) => {
-                        match assignment.variables().iter().next() {
-                            Some(Var::Expression(var_expression)) => {
-                                matches!(
-                                    var_expression.prefix(),
-                                    Prefix::Expression(Expression::Parentheses { .. })
-                                )
-                            }
-                            _ => false,
-                        }
-                    }
-                    _ => false,
-                }
-            }
-            _ => false,
+        // If the next statement starts with a parenthesised expression, and this statement ends with an expression,
+        // then removing a semicolon would lead to ambiguous syntax, so one is required between them
+        // When only formatting whitespace, semicolons are kept exactly where they were
+        let require_semicolon = if ctx.config().whitespace_only {
+            semi.is_some()
+        } else {
+            stmt_ends_with_expression(&stmt)
+                && matches!(stmt_iterator.peek(), Some((next_stmt, _)) if stmt_starts_with_parentheses(next_stmt))
         };
 
         // If we have a semicolon, we need to push all the trailing trivia from the statement
@@ -496,9 +789,18 @@ pub fn format_block(ctx: &Context, block: &Block, shape: Shape) -> Block {
     // Drop the stmt_iterator as we do not need it anymore and we still need to use `block`
     drop(stmt_iterator);
 
+    if ctx.config().align_trailing_comments {
+        formatted_statements =
+            align_trailing_comments(&ctx, formatted_statements, &formatted_flags);
+    }
+
+    let mut last_stmt_formatted = false;
     let formatted_last_stmt = match block.last_stmt_with_semicolon() {
         Some((last_stmt, semi)) => {
-            ctx = ctx.check_toggle_formatting(last_stmt);
+            ctx = ctx
+                .check_toggle_formatting(last_stmt)
+                .check_layout_directive(last_stmt);
+            last_stmt_formatted = ctx.should_format_node(last_stmt);
 
             let shape = shape.reset();
             let mut last_stmt = format_last_stmt(&ctx, last_stmt, shape);
@@ -515,13 +817,17 @@ pub fn format_block(ctx: &Context, block: &Block, shape: Shape) -> Block {
                     last_stmt = updated_last_stmt;
 
                     // We want to keep any old comments on the semicolon token, otherwise we will lose it
-                    // We will do a hack here, where we replace the semicolon with an empty symbol
-                    let semicolon_token = format_symbol(
-                        &ctx,
-                        semi,
-                        &TokenReference::new(vec![], Token::new(TokenType::spaces(0)), vec![]),
-                        shape,
-                    )
+                    // We will do a hack here, where we replace the semicolon with an empty symbol,
+                    // unless we are only formatting whitespace
+                    let semicolon_token = match ctx.config().whitespace_only {
+                        true => crate::fmt_symbol!(&ctx, semi, ";", shape),
+                        false => format_symbol(
+                            &ctx,
+                            semi,
+                            &TokenReference::new(vec![], Token::new(TokenType::spaces(0)), vec![]),
+                            shape,
+                        ),
+                    }
                     .update_trailing_trivia(FormatTriviaType::Append(trivia));
                     Some(semicolon_token)
                 }
@@ -532,6 +838,19 @@ pub fn format_block(ctx: &Context, block: &Block, shape: Shape) -> Block {
         None => None,
     };
 
+    // Only the requires at the top of the file are separated from the rest of the code
+    if ctx.config().blank_line_after_requires && shape.indent().block_indent() == 0 {
+        separate_requires(
+            &ctx,
+            block,
+            &mut formatted_statements,
+            &formatted_flags,
+            formatted_last_stmt
+                .as_ref()
+                .map(|(last_stmt, _)| (last_stmt, last_stmt_formatted)),
+        );
+    }
+
     block
         .to_owned()
         .with_stmts(formatted_statements)
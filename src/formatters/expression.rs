@@ -1,5 +1,5 @@
 #[cfg(feature = "luau")]
-use full_moon::ast::types::IfExpression;
+use full_moon::ast::types::{IfExpression, TypeAssertion};
 use full_moon::{
     ast::{
         span::ContainedSpan, BinOp, Call, Expression, Index, Prefix, Suffix, UnOp, Value, Var,
@@ -11,7 +11,7 @@ use full_moon::{
 use std::boxed::Box;
 
 #[cfg(feature = "luau")]
-use crate::formatters::luau::format_type_assertion;
+use crate::formatters::{luau::format_type_assertion, trivia::strip_trailing_trivia};
 use crate::{
     context::{create_indent_trivia, create_newline_trivia, Context},
     fmt_symbol,
@@ -61,6 +61,8 @@ pub fn format_binop(ctx: &Context, binop: &BinOp, shape: Shape) -> BinOp {
         GreaterThanEqual = " >= ",
         LessThan = " < ",
         LessThanEqual = " <= ",
+        // The trailing space must always be kept, even when hanging: without it, a following unary minus
+        // (`a - -b`) would create `--`, turning the rest of the line into a comment
         Minus = " - ",
         Or = " or ",
         Percent = " % ",
@@ -113,6 +115,49 @@ fn check_excess_parentheses(internal_expression: &Expression) -> bool {
     }
 }
 
+/// The padding to place inside of the parentheses of an `Expression::Parentheses` node.
+/// Parentheses used as a prefix, such as `(a or b).c`, are never padded.
+fn parentheses_padding(ctx: &Context, context: ExpressionContext) -> &'static str {
+    if ctx.config().spaces_inside_parentheses && !matches!(context, ExpressionContext::Prefix) {
+        " "
+    } else {
+        ""
+    }
+}
+
+/// Formats the parentheses of an `Expression::Parentheses` node, placing the given padding inside of them
+fn format_parentheses(
+    ctx: &Context,
+    contained: &ContainedSpan,
+    padding: &str,
+    shape: Shape,
+) -> ContainedSpan {
+    let (start_parens, end_parens) = contained.tokens();
+
+    ContainedSpan::new(
+        fmt_symbol!(ctx, start_parens, &format!("({}", padding), shape),
+        fmt_symbol!(ctx, end_parens, &format!("{})", padding), shape),
+    )
+}
+
+/// Formats the type assertion following a value, if present. Also returns the shape to format the value with, which
+/// leaves room for the assertion, so that the value is expanded (e.g. a multiline table) if the assertion would not fit
+#[cfg(feature = "luau")]
+fn format_value_type_assertion(
+    ctx: &Context,
+    type_assertion: &Option<TypeAssertion>,
+    shape: Shape,
+) -> (Option<TypeAssertion>, Shape) {
+    match type_assertion {
+        Some(type_assertion) => {
+            let type_assertion = format_type_assertion(ctx, type_assertion, shape);
+            let shape = shape.take_first_line(&strip_trailing_trivia(&type_assertion));
+            (Some(type_assertion), shape)
+        }
+        None => (None, shape),
+    }
+}
+
 /// Formats an Expression node
 pub fn format_expression(ctx: &Context, expression: &Expression, shape: Shape) -> Expression {
     format_expression_internal(ctx, expression, ExpressionContext::Standard, shape)
@@ -130,20 +175,24 @@ fn format_expression_internal(
             value,
             #[cfg(feature = "luau")]
             type_assertion,
-        } => Expression::Value {
-            value: Box::new(format_value(ctx, value, shape)),
+        } => {
             #[cfg(feature = "luau")]
-            type_assertion: type_assertion
-                .as_ref()
-                .map(|assertion| format_type_assertion(ctx, assertion, shape)),
-        },
+            let (type_assertion, shape) = format_value_type_assertion(ctx, type_assertion, shape);
+
+            Expression::Value {
+                value: Box::new(format_value(ctx, value, shape)),
+                #[cfg(feature = "luau")]
+                type_assertion,
+            }
+        }
         Expression::Parentheses {
             contained,
             expression,
         } => {
             // Examine whether the internal expression requires parentheses
             // If not, just format and return the internal expression. Otherwise, format the parentheses
-            let use_internal_expression = check_excess_parentheses(expression);
+            let use_internal_expression =
+                !ctx.config().whitespace_only && check_excess_parentheses(expression);
 
             // If the context is for a prefix, we should always keep the parentheses, as they are always required
             if use_internal_expression && !matches!(context, ExpressionContext::Prefix) {
@@ -161,50 +210,23 @@ fn format_expression_internal(
                 format_expression(ctx, expression, shape)
                     .update_trailing_trivia(FormatTriviaType::Append(trailing_comments))
             } else {
+                let padding = parentheses_padding(ctx, context);
                 Expression::Parentheses {
-                    contained: format_contained_span(ctx, contained, shape),
-                    expression: Box::new(format_expression(ctx, expression, shape + 1)), // 1 = opening parentheses
+                    contained: format_parentheses(ctx, contained, padding, shape),
+                    expression: Box::new(format_expression(
+                        ctx,
+                        expression,
+                        shape + 1 + padding.len(), // 1 = opening parentheses
+                    )),
                 }
             }
         }
         Expression::UnaryOperator { unop, expression } => {
             let unop = format_unop(ctx, unop, shape);
             let shape = shape + strip_leading_trivia(&unop).to_string().len();
-            let mut expression = format_expression(ctx, expression, shape);
-
-            // Special case: if we have `- -foo`, or `-(-foo)` where we have already removed the parentheses, then
-            // it will lead to `--foo`, which is invalid syntax. We must explicitly add/keep the parentheses `-(-foo)`.
-            if let UnOp::Minus(_) = unop {
-                let require_parentheses = match expression {
-                    Expression::UnaryOperator {
-                        unop: UnOp::Minus(_),
-                        ..
-                    } => true,
-
-                    Expression::Value { ref value, .. } => matches!(
-                        **value,
-                        Value::ParenthesesExpression(Expression::UnaryOperator {
-                            unop: UnOp::Minus(_),
-                            ..
-                        })
-                    ),
+            let expression = format_expression(ctx, expression, shape);
 
-                    _ => false,
-                };
-
-                if require_parentheses {
-                    let (new_expression, trailing_comments) =
-                        trivia_util::take_expression_trailing_comments(&expression);
-                    expression = Expression::Parentheses {
-                        contained: ContainedSpan::new(
-                            TokenReference::symbol("(").unwrap(),
-                            TokenReference::symbol(")").unwrap(),
-                        )
-                        .update_trailing_trivia(FormatTriviaType::Append(trailing_comments)),
-                        expression: Box::new(new_expression),
-                    }
-                }
-            }
+            let (unop, expression) = separate_minus_operators(ctx, unop, expression);
 
             Expression::UnaryOperator {
                 unop,
@@ -212,6 +234,18 @@ fn format_expression_internal(
             }
         }
         Expression::BinaryOperator { lhs, binop, rhs } => {
+            if ctx.config().merge_string_concatenation {
+                if let Some(merged) = merge_string_concatenation(expression) {
+                    return format_expression(ctx, &merged, shape);
+                }
+            }
+
+            if ctx.config().parenthesise_mixed_and_or {
+                if let Some(parenthesised) = parenthesise_mixed_and_or(expression) {
+                    return format_expression(ctx, &parenthesised, shape);
+                }
+            }
+
             let lhs = format_expression(ctx, lhs, shape);
             let binop = format_binop(ctx, binop, shape);
             let shape = shape.take_last_line(&lhs) + binop.to_string().len();
@@ -225,6 +259,152 @@ fn format_expression_internal(
     }
 }
 
+/// If the given expression is an `or` operation with an `and` operation directly as one of its operands, i.e. `a and b or c`,
+/// returns the expression with parentheses placed around those operands: `(a and b) or c`. As `and` binds tighter than `or`,
+/// this does not change how the expression is evaluated. Returns `None` if there is nothing to parenthesise, or if
+/// the expression contains comments.
+fn parenthesise_mixed_and_or(expression: &Expression) -> Option<Expression> {
+    let (lhs, binop, rhs) = match expression {
+        Expression::BinaryOperator {
+            lhs,
+            binop: binop @ BinOp::Or(_),
+            rhs,
+        } => (lhs, binop, rhs),
+        _ => return None,
+    };
+
+    let is_and = |operand: &Expression| {
+        matches!(
+            operand,
+            Expression::BinaryOperator {
+                binop: BinOp::And(_),
+                ..
+            }
+        )
+    };
+
+    if !(is_and(lhs) || is_and(rhs)) || contains_comments(expression) {
+        return None;
+    }
+
+    let parenthesise = |operand: &Expression| {
+        if is_and(operand) {
+            Expression::Parentheses {
+                contained: ContainedSpan::new(
+                    TokenReference::symbol("(").unwrap(),
+                    TokenReference::symbol(")").unwrap(),
+                ),
+                expression: Box::new(operand.to_owned()),
+            }
+        } else {
+            operand.to_owned()
+        }
+    };
+
+    Some(Expression::BinaryOperator {
+        lhs: Box::new(parenthesise(lhs)),
+        binop: binop.to_owned(),
+        rhs: Box::new(parenthesise(rhs)),
+    })
+}
+
+/// Collects the raw contents of every quoted string literal in a chain of `..` concatenations, i.e. `"a" .. "b" .. "c"`.
+/// Parentheses around operands are ignored. Returns `None` if any operand of the chain is not a single or double
+/// quoted string literal.
+fn collect_concatenated_literals(
+    expression: &Expression,
+    literals: &mut Vec<String>,
+) -> Option<()> {
+    match expression {
+        Expression::BinaryOperator {
+            lhs,
+            binop: BinOp::TwoDots(_),
+            rhs,
+        } => {
+            collect_concatenated_literals(lhs, literals)?;
+            collect_concatenated_literals(rhs, literals)
+        }
+        Expression::Parentheses { expression, .. } => {
+            collect_concatenated_literals(expression, literals)
+        }
+        Expression::Value {
+            value,
+            #[cfg(feature = "luau")]
+            type_assertion: None,
+        } => match &**value {
+            Value::String(token) => match token.token_type() {
+                TokenType::StringLiteral {
+                    literal,
+                    quote_type: StringLiteralQuoteType::Double | StringLiteralQuoteType::Single,
+                    ..
+                } => {
+                    literals.push(literal.to_string());
+                    Some(())
+                }
+                _ => None,
+            },
+            Value::ParenthesesExpression(expression) => {
+                collect_concatenated_literals(expression, literals)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether the literal ends with a decimal escape sequence (e.g. `\1`), which would absorb any digits
+/// directly following it if another literal were appended onto it.
+fn ends_with_decimal_escape(literal: &str) -> bool {
+    let without_digits = literal.trim_end_matches(|c: char| c.is_ascii_digit());
+    if without_digits.len() == literal.len() {
+        return false;
+    }
+
+    // The digits are only an escape if they are preceded by an odd number of backslashes
+    let backslashes = without_digits.len() - without_digits.trim_end_matches('\\').len();
+    backslashes % 2 == 1
+}
+
+/// Attempts to merge a concatenation consisting purely of quoted string literals into a single string literal,
+/// i.e. `"a" .. "\n" .. "b"` becomes `"a\nb"`. Returns `None` if the concatenation cannot be safely merged.
+/// The merged literal is created with double quotes, and will have its quotes and escapes normalised when formatted.
+fn merge_string_concatenation(expression: &Expression) -> Option<Expression> {
+    if contains_comments(expression) {
+        return None;
+    }
+
+    let mut literals = Vec::new();
+    collect_concatenated_literals(expression, &mut literals)?;
+
+    let mut merged = String::new();
+    for literal in literals {
+        // `\z` skips all whitespace following it, which may change meaning once the next literal is joined on
+        if literal.contains("\\z") {
+            return None;
+        }
+
+        if literal.starts_with(|c: char| c.is_ascii_digit()) && ends_with_decimal_escape(&merged) {
+            return None;
+        }
+
+        merged.push_str(&literal);
+    }
+
+    Some(Expression::Value {
+        value: Box::new(Value::String(TokenReference::new(
+            vec![],
+            Token::new(TokenType::StringLiteral {
+                literal: merged.into(),
+                multi_line: None,
+                quote_type: StringLiteralQuoteType::Double,
+            }),
+            vec![],
+        ))),
+        #[cfg(feature = "luau")]
+        type_assertion: None,
+    })
+}
+
 /// Determines whether the provided [`Expression`] is a brackets string, i.e. `[[string]]`
 pub fn is_brackets_string(expression: &Expression) -> bool {
     if let Expression::Value { value, .. } = expression {
@@ -279,7 +459,7 @@ pub fn format_index(ctx: &Context, index: &Index, shape: Shape) -> Index {
                     brackets,
                     expression,
                 }
-            } else if is_brackets_string(expression) {
+            } else if is_brackets_string(expression) || ctx.config().spaces_inside_index_brackets {
                 Index::Brackets {
                     brackets: format_contained_span(ctx, brackets, shape),
                     expression: format_expression(ctx, expression, shape + 2) // 2 = "[ "
@@ -347,6 +527,44 @@ pub fn format_suffix(
     }
 }
 
+/// Moves comments taken from before a suffix in a chain to the end of the previous segment, which is either the last
+/// formatted suffix or the prefix. Returns whether the previous segment ends with a single line comment, in which case
+/// the next suffix must be placed on a new line.
+pub fn attach_comments_to_previous_segment(
+    prefix: &mut Prefix,
+    suffixes: &mut Vec<Suffix>,
+    comments: Vec<Token>,
+) -> bool {
+    if !comments.is_empty() {
+        let comments = comments
+            .into_iter()
+            .flat_map(|comment| vec![Token::new(TokenType::spaces(1)), comment])
+            .collect();
+
+        match suffixes.pop() {
+            Some(suffix) => {
+                suffixes.push(suffix.update_trailing_trivia(FormatTriviaType::Append(comments)))
+            }
+            None => {
+                *prefix = prefix.update_trailing_trivia(FormatTriviaType::Append(comments));
+            }
+        }
+    }
+
+    let trailing_trivia = match suffixes.last() {
+        Some(suffix) => trivia_util::suffix_trailing_trivia(suffix),
+        None => match prefix {
+            Prefix::Name(token_reference) => token_reference.trailing_trivia().cloned().collect(),
+            Prefix::Expression(expression) => {
+                trivia_util::get_expression_trailing_trivia(expression)
+            }
+            other => panic!("unknown node {:?}", other),
+        },
+    };
+
+    trivia_util::trivia_contains_single_line_comment(&trailing_trivia)
+}
+
 /// Formats an [`IfExpression`] node
 #[cfg(feature = "luau")]
 fn format_if_expression(
@@ -422,13 +640,28 @@ pub fn format_var_expression(
     var_expression: &VarExpression,
     shape: Shape,
 ) -> VarExpression {
-    let formatted_prefix = format_prefix(ctx, var_expression.prefix(), shape);
+    let mut formatted_prefix = format_prefix(ctx, var_expression.prefix(), shape);
     let mut shape = shape + strip_leading_trivia(&formatted_prefix).to_string().len();
 
     let mut formatted_suffixes = Vec::new();
     let mut suffixes = var_expression.suffixes().peekable();
 
     while let Some(suffix) = suffixes.next() {
+        // Comments before the name of the suffix are moved to the end of the previous segment.
+        // If the previous segment ends with a single line comment, the suffix is hung onto a new line
+        let (suffix, comments) = trivia_util::take_suffix_leading_comments(suffix);
+        let hang_after_comment = attach_comments_to_previous_segment(
+            &mut formatted_prefix,
+            &mut formatted_suffixes,
+            comments,
+        );
+        let current_shape = if hang_after_comment {
+            shape = shape.reset();
+            shape.increment_additional_indent()
+        } else {
+            shape
+        };
+
         // If the suffix after this one is something like `.foo` or `:foo` - this affects removing parentheses
         let ambiguous_next_suffix = if matches!(
             suffixes.peek(),
@@ -439,7 +672,14 @@ pub fn format_var_expression(
             FunctionCallNextNode::None
         };
 
-        let suffix = format_suffix(ctx, suffix, shape, ambiguous_next_suffix);
+        let mut suffix = format_suffix(ctx, &suffix, current_shape, ambiguous_next_suffix);
+        if hang_after_comment {
+            suffix = suffix.update_leading_trivia(FormatTriviaType::Append(vec![
+                create_newline_trivia(ctx),
+                create_indent_trivia(ctx, current_shape),
+            ]));
+        }
+
         shape = shape.take_last_line(&suffix);
         formatted_suffixes.push(suffix);
     }
@@ -447,7 +687,9 @@ pub fn format_var_expression(
     VarExpression::new(formatted_prefix).with_suffixes(formatted_suffixes)
 }
 
-/// Formats an UnOp Node
+/// Formats a [`UnOp`], which is the single source of truth for the spacing of unary operators:
+/// `not` is always followed by a single space, whilst `-` and `#` are attached directly to their operand.
+/// See [`separate_minus_operators`] for the special case of `- -foo`.
 pub fn format_unop(ctx: &Context, unop: &UnOp, shape: Shape) -> UnOp {
     fmt_op!(ctx, UnOp, unop, shape, {
         Minus = "-",
@@ -456,6 +698,57 @@ pub fn format_unop(ctx: &Context, unop: &UnOp, shape: Shape) -> UnOp {
     })
 }
 
+/// Ensures a unary minus followed by another unary minus is not printed as `--`, which would start a comment.
+/// If we have `- -foo`, or `-(-foo)` where we have already removed the parentheses, then it will lead to `--foo`,
+/// which is invalid syntax. We must explicitly add/keep the parentheses `-(-foo)`. When only formatting whitespace,
+/// the operators are separated with a space instead: `- -foo`.
+fn separate_minus_operators(
+    ctx: &Context,
+    mut unop: UnOp,
+    mut expression: Expression,
+) -> (UnOp, Expression) {
+    if let UnOp::Minus(_) = unop {
+        let require_parentheses = match expression {
+            Expression::UnaryOperator {
+                unop: UnOp::Minus(_),
+                ..
+            } => true,
+
+            Expression::Value { ref value, .. } => matches!(
+                **value,
+                Value::ParenthesesExpression(Expression::UnaryOperator {
+                    unop: UnOp::Minus(_),
+                    ..
+                })
+            ),
+
+            _ => false,
+        };
+
+        if require_parentheses && ctx.config().whitespace_only {
+            // We cannot add parentheses, so separate the operators with a space instead: `- -foo`
+            if let UnOp::Minus(token) = &unop {
+                unop = UnOp::Minus(token.update_trailing_trivia(FormatTriviaType::Append(vec![
+                    Token::new(TokenType::spaces(1)),
+                ])));
+            }
+        } else if require_parentheses {
+            let (new_expression, trailing_comments) =
+                trivia_util::take_expression_trailing_comments(&expression);
+            expression = Expression::Parentheses {
+                contained: ContainedSpan::new(
+                    TokenReference::symbol("(").unwrap(),
+                    TokenReference::symbol(")").unwrap(),
+                )
+                .update_trailing_trivia(FormatTriviaType::Append(trailing_comments)),
+                expression: Box::new(new_expression),
+            }
+        }
+    }
+
+    (unop, expression)
+}
+
 /// Pushes a [`BinOp`] onto a newline, and indent its depending on indent_level.
 /// Preserves any leading comments, and moves trailing comments to before the BinOp.
 /// Also takes in the [`Expression`] present on the RHS of the BinOp - this is needed so that we can take any
@@ -682,6 +975,18 @@ fn hang_binop_expression(
 ) -> Expression {
     let full_expression = expression.to_owned();
 
+    if ctx.config().merge_string_concatenation {
+        if let Some(merged) = merge_string_concatenation(&full_expression) {
+            return format_expression(ctx, &merged, shape);
+        }
+    }
+
+    if ctx.config().parenthesise_mixed_and_or {
+        if let Some(parenthesised) = parenthesise_mixed_and_or(&full_expression) {
+            return hang_binop_expression(ctx, parenthesised, top_binop, shape, lhs_range);
+        }
+    }
+
     match expression {
         Expression::BinaryOperator { lhs, binop, rhs } => {
             // Keep grouping together all operators with the same precedence level as the main BinOp
@@ -731,7 +1036,7 @@ fn hang_binop_expression(
                                 lhs_shape,
                                 lhs_range,
                             ),
-                            format_expression(ctx, &*rhs, rhs_shape),
+                            hang_binop_operand(ctx, *rhs, rhs_shape),
                         ),
                         ExpressionSide::Right => (
                             format_expression(ctx, &*lhs, lhs_shape),
@@ -785,6 +1090,96 @@ fn hang_binop_expression(
     }
 }
 
+/// Formats the operand of a hung binary expression which is not itself being hung at the current precedence level,
+/// e.g. the `b and c` in `a or b and c or d`. If the operand is a binary expression at a different precedence level
+/// which does not fit on the line, then it is hung one indent level further than the current chain.
+fn hang_binop_operand(ctx: &Context, operand: Expression, shape: Shape) -> Expression {
+    let formatted = format_expression(ctx, &operand, shape);
+
+    match operand {
+        Expression::BinaryOperator { ref binop, .. }
+            if shape.take_first_line(&formatted).over_budget() =>
+        {
+            let binop = binop.to_owned();
+            hang_binop_expression(
+                ctx,
+                operand,
+                binop,
+                shape.increment_additional_indent(),
+                None,
+            )
+        }
+        _ => formatted,
+    }
+}
+
+/// Hangs a `cond and a or b` ternary idiom as a unit, placing both the `and` and the `or` onto new lines at the same
+/// indent level. Returns `None` if the expression is not a ternary idiom, or if it contains comments.
+fn hang_ternary_idiom(
+    ctx: &Context,
+    expression: &Expression,
+    shape: Shape,
+    lhs_range: Option<LeftmostRangeHang>,
+) -> Option<Expression> {
+    let (condition, and_binop, value, or_binop, fallback) = match expression {
+        Expression::BinaryOperator {
+            lhs,
+            binop: or_binop @ BinOp::Or(_),
+            rhs: fallback,
+        } => match &**lhs {
+            Expression::BinaryOperator {
+                lhs: condition,
+                binop: and_binop @ BinOp::And(_),
+                rhs: value,
+            } => (condition, and_binop, value, or_binop, fallback),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    if contains_comments(expression) {
+        return None;
+    }
+
+    // Binary expressions are hung one indent level further, whilst other operands, such as a nested ternary idiom
+    // within parentheses, are hung in place if they do not fit on the line
+    let hang_operand = |operand: &Expression, shape: Shape| match operand {
+        Expression::BinaryOperator { .. } => hang_binop_operand(ctx, operand.to_owned(), shape),
+        _ => {
+            let formatted = format_expression(ctx, operand, shape);
+            if shape.take_first_line(&formatted).over_budget() {
+                format_hanging_expression_(ctx, operand, shape, ExpressionContext::Standard, None)
+            } else {
+                formatted
+            }
+        }
+    };
+
+    let condition = format_hanging_expression_(
+        ctx,
+        condition,
+        shape,
+        ExpressionContext::Standard,
+        lhs_range,
+    );
+
+    let and_binop = hang_binop(ctx, and_binop.to_owned(), shape, value);
+    let value = hang_operand(value, shape.reset() + 4); // 4 = "and "
+
+    let or_binop = hang_binop(ctx, or_binop.to_owned(), shape, fallback);
+    let fallback = hang_operand(fallback, shape.reset() + 3); // 3 = "or "
+
+    Some(Expression::BinaryOperator {
+        lhs: Box::new(Expression::BinaryOperator {
+            lhs: Box::new(condition),
+            binop: and_binop,
+            rhs: Box::new(value),
+        }),
+        binop: or_binop,
+        rhs: Box::new(fallback),
+    })
+}
+
 /// Internal expression formatter, where the binop is also hung
 fn format_hanging_expression_(
     ctx: &Context,
@@ -801,6 +1196,9 @@ fn format_hanging_expression_(
             #[cfg(feature = "luau")]
             type_assertion,
         } => {
+            #[cfg(feature = "luau")]
+            let (type_assertion, shape) = format_value_type_assertion(ctx, type_assertion, shape);
+
             let value = Box::new(match &**value {
                 Value::ParenthesesExpression(expression) => {
                     Value::ParenthesesExpression(format_hanging_expression_(
@@ -823,9 +1221,7 @@ fn format_hanging_expression_(
             Expression::Value {
                 value,
                 #[cfg(feature = "luau")]
-                type_assertion: type_assertion
-                    .as_ref()
-                    .map(|assertion| format_type_assertion(ctx, assertion, shape)),
+                type_assertion,
             }
         }
         Expression::Parentheses {
@@ -840,7 +1236,8 @@ fn format_hanging_expression_(
 
             // Examine whether the internal expression requires parentheses
             // If not, just format and return the internal expression. Otherwise, format the parentheses
-            let use_internal_expression = check_excess_parentheses(expression);
+            let use_internal_expression =
+                !ctx.config().whitespace_only && check_excess_parentheses(expression);
 
             // If the context is for a prefix, we should always keep the parentheses, as they are always required
             if use_internal_expression && !matches!(expression_context, ExpressionContext::Prefix) {
@@ -852,21 +1249,28 @@ fn format_hanging_expression_(
                     lhs_range,
                 )
             } else {
-                let contained = format_contained_span(ctx, contained, lhs_shape);
+                let padding = parentheses_padding(ctx, expression_context);
 
                 // Provide a sample formatting to see how large it is
                 // Examine the expression itself to see if needs to be split onto multiple lines
-                let formatted_expression = format_expression(ctx, expression, lhs_shape + 1); // 1 = opening parentheses
+                let formatted_expression =
+                    format_expression(ctx, expression, lhs_shape + 1 + padding.len()); // 1 = opening parentheses
 
                 let expression_str = formatted_expression.to_string();
-                if !lhs_shape.add_width(2 + expression_str.len()).over_budget() {
+                if !lhs_shape
+                    .add_width(2 + 2 * padding.len() + expression_str.len())
+                    .over_budget()
+                {
                     // The expression inside the parentheses is small, we do not need to break it down further
                     return Expression::Parentheses {
-                        contained,
+                        contained: format_parentheses(ctx, contained, padding, lhs_shape),
                         expression: Box::new(formatted_expression),
                     };
                 }
 
+                // The expression is hung onto new lines inside the parentheses, so we do not pad them
+                let contained = format_contained_span(ctx, contained, lhs_shape);
+
                 // Update the expression shape to be used inside the parentheses, applying the indent increase
                 // Use the original `shape` rather than the LeftmostRangeHang-determined shape, because we are now
                 // indenting the internal expression, which is not part of the hang
@@ -905,6 +1309,7 @@ fn format_hanging_expression_(
             let shape = shape + strip_leading_trivia(&unop).to_string().len();
             let expression =
                 format_hanging_expression_(ctx, expression, shape, expression_context, lhs_range);
+            let (unop, expression) = separate_minus_operators(ctx, unop, expression);
 
             Expression::UnaryOperator {
                 unop,
@@ -912,6 +1317,30 @@ fn format_hanging_expression_(
             }
         }
         Expression::BinaryOperator { lhs, binop, rhs } => {
+            if ctx.config().merge_string_concatenation {
+                if let Some(merged) = merge_string_concatenation(expression) {
+                    return format_expression(ctx, &merged, shape);
+                }
+            }
+
+            if ctx.config().parenthesise_mixed_and_or {
+                if let Some(parenthesised) = parenthesise_mixed_and_or(expression) {
+                    return format_hanging_expression_(
+                        ctx,
+                        &parenthesised,
+                        shape,
+                        expression_context,
+                        lhs_range,
+                    );
+                }
+            }
+
+            if ctx.config().hang_ternary_idiom {
+                if let Some(hung) = hang_ternary_idiom(ctx, expression, shape, lhs_range) {
+                    return hung;
+                }
+            }
+
             // Don't format the lhs and rhs here, because it will be handled later when hang_binop_expression calls back for a Value
             let lhs =
                 hang_binop_expression(ctx, *lhs.to_owned(), binop.to_owned(), shape, lhs_range);
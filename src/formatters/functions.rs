@@ -2,21 +2,25 @@ use full_moon::ast::{
     punctuated::{Pair, Punctuated},
     span::ContainedSpan,
     Call, Expression, FunctionArgs, FunctionBody, FunctionCall, FunctionDeclaration, FunctionName,
-    LocalFunction, MethodCall, Parameter, Suffix, Value,
+    Index, LocalAssignment, LocalFunction, MethodCall, Parameter, Suffix, Value,
 };
-use full_moon::tokenizer::{Symbol, Token, TokenReference, TokenType};
+use full_moon::node::Node;
+use full_moon::tokenizer::{Token, TokenReference, TokenType};
 use std::boxed::Box;
 
 #[cfg(feature = "luau")]
 use crate::formatters::luau::{format_generic_declaration, format_type_specifier};
 use crate::{
-    context::{create_indent_trivia, create_newline_trivia, Context},
+    context::{create_indent_trivia, create_newline_trivia, Context, LayoutDirective},
     fmt_symbol,
     formatters::{
         block::format_block,
-        expression::{format_expression, format_prefix, format_suffix, hang_expression},
+        expression::{
+            attach_comments_to_previous_segment, format_expression, format_prefix, format_suffix,
+            hang_expression,
+        },
         general::{
-            format_contained_span, format_end_token, format_punctuated, format_symbol,
+            format_end_token, format_punctuated, format_singleline_contained_span,
             format_token_reference, EndTokenType,
         },
         table::format_table_constructor,
@@ -28,6 +32,8 @@ use crate::{
     },
     shape::Shape,
 };
+#[cfg(feature = "luau")]
+use full_moon::ast::types::TypeSpecifier;
 
 /// Formats an Anonymous Function
 /// This doesn't have its own struct, but it is part of Value::Function
@@ -37,7 +43,11 @@ pub fn format_anonymous_function(
     function_body: &FunctionBody,
     shape: Shape,
 ) -> (TokenReference, FunctionBody) {
-    let function_token = fmt_symbol!(ctx, function_token, "function", shape);
+    let function_token = if ctx.config().space_after_anonymous_function {
+        fmt_symbol!(ctx, function_token, "function ", shape)
+    } else {
+        fmt_symbol!(ctx, function_token, "function", shape)
+    };
     let function_body = format_function_body(ctx, function_body, false, shape.reset()); // TODO: do we want to reset this shape?
 
     (function_token, function_body)
@@ -93,6 +103,34 @@ fn is_complex_arg(value: &Value) -> bool {
     value.to_string().trim().contains('\n')
 }
 
+/// Whether the last of the formatted arguments is an expanded anonymous function, i.e. a callback, and all the
+/// arguments before it fit on a single line
+fn has_trailing_callback(arguments: &[Expression]) -> bool {
+    match arguments.split_last() {
+        Some((
+            Expression::Value {
+                value,
+                #[cfg(feature = "luau")]
+                    type_assertion: None,
+            },
+            other_arguments,
+        )) => {
+            let is_expanded_function = match &**value {
+                Value::Function((_, function_body)) => {
+                    !trivia_util::is_function_empty(function_body)
+                }
+                _ => false,
+            };
+
+            is_expanded_function
+                && !other_arguments
+                    .iter()
+                    .any(|argument| strip_trivia(argument).to_string().contains('\n'))
+        }
+        _ => false,
+    }
+}
+
 /// Formats a FunctionArgs node.
 /// [`call_next_node`] provides information about the node after the FunctionArgs. This only matters if the configuration specifies no call parentheses.
 pub fn format_function_args(
@@ -342,16 +380,80 @@ pub fn format_function_args(
                     if singleline_shape.sub_width(1).over_budget() {
                         is_multiline = true;
                     }
+
+                    // If the arguments all fit on a single line, check that they are not wider than the maximum inline width,
+                    // or nested too deeply, unless a collapse directive is present
+                    if !is_multiline && ctx.layout_directive() != Some(LayoutDirective::Collapse) {
+                        let arguments_string = first_iter_formatted_arguments
+                            .iter()
+                            .map(|argument| strip_trivia(argument).to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        // A single table argument hugged by the parentheses is at the same depth as the call,
+                        // so it is left to decide whether it should be expanded itself
+                        let hugged_table = match arguments.iter().next() {
+                            Some(Expression::Value { value, .. })
+                                if ctx.config().hug_table_argument && arguments.len() == 1 =>
+                            {
+                                match &**value {
+                                    Value::TableConstructor(table_constructor) => {
+                                        Some(table_constructor)
+                                    }
+                                    _ => None,
+                                }
+                            }
+                            _ => None,
+                        };
+                        let nesting_depth = match hugged_table {
+                            Some(table_constructor) => {
+                                trivia_util::table_nesting_depth(table_constructor)
+                            }
+                            None => trivia_util::function_args_nesting_depth(function_args),
+                        };
+                        if !arguments_string.contains('\n')
+                            && (arguments_string.len() > ctx.config().max_inline_width
+                                || nesting_depth > ctx.config().max_inline_nesting_depth)
+                        {
+                            is_multiline = true;
+                        }
+                    }
                 }
             }
 
+            // Keep a trailing callback attached to the call if configured, rather than hanging the arguments
+            let attach_callback = ctx.config().attach_trailing_callback
+                && !force_mutliline
+                && has_trailing_callback(&first_iter_formatted_arguments);
+            if attach_callback {
+                is_multiline = false;
+            }
+
+            // An expand directive always expands the arguments, if there are any
+            if ctx.layout_directive() == Some(LayoutDirective::Expand) && !arguments.is_empty() {
+                is_multiline = true;
+            }
+
+            let single_table_argument =
+                arguments.len() == 1 && is_table_constructor(arguments.iter().next().unwrap());
+
+            // If we should not hug a single table argument with the parentheses, but the table has been expanded,
+            // then we expand the arguments instead, placing the table on its own line
+            if !ctx.config().hug_table_argument
+                && single_table_argument
+                && first_iter_formatted_arguments[0]
+                    .to_string()
+                    .contains('\n')
+            {
+                is_multiline = true;
+            }
+
             // Handle special case: we want to go multiline, but we have a single argument which is a table constructor
             // In this case, we want to hug the table braces with the parentheses.
             // To do this, we format single line, but include the closing parentheses in the shape
             let hug_table_constructor = is_multiline
                 && !force_mutliline
-                && arguments.len() == 1
-                && is_table_constructor(arguments.iter().next().unwrap());
+                && single_table_argument
+                && ctx.config().hug_table_argument;
 
             if is_multiline && !hug_table_constructor {
                 // Format start and end brace properly with correct trivia
@@ -414,6 +516,8 @@ pub fn format_function_args(
 
                             Some(symbol)
                         }
+                        // Unlike tables, Lua does not allow a trailing comma after the last argument
+                        // (`call(a, b,)` is a syntax error), so we only ever emit the newline here
                         None => Some(TokenReference::new(
                             trailing_comments,
                             create_newline_trivia(ctx),
@@ -436,9 +540,22 @@ pub fn format_function_args(
                 // parentheses aswell. Otherwise, we just use 1 = opening parentheses.
                 let shape_increment = if hug_table_constructor { 2 } else { 1 };
 
-                let parentheses = format_contained_span(ctx, parentheses, shape);
-                let mut arguments =
-                    format_punctuated(ctx, arguments, shape + shape_increment, format_expression);
+                // An attached trailing callback may have its body, and its `end`, indented an extra level
+                let callback = arguments.iter().last();
+                let indent_callback = attach_callback && ctx.config().indent_trailing_callback_body;
+
+                let parentheses = format_singleline_contained_span(ctx, parentheses, shape);
+                let mut arguments = format_punctuated(
+                    ctx,
+                    arguments,
+                    shape + shape_increment,
+                    |ctx, argument, shape| match callback {
+                        Some(callback) if indent_callback && std::ptr::eq(argument, callback) => {
+                            format_expression(ctx, argument, shape.increment_additional_indent())
+                        }
+                        _ => format_expression(ctx, argument, shape),
+                    },
+                );
 
                 // HACK: if there was more than one newline before each argument, then it will be incorrectly preserved
                 // leading to weird formatting (https://github.com/JohnnyMorganz/StyLua/issues/290#issuecomment-964428535)
@@ -464,8 +581,12 @@ pub fn format_function_args(
         }
 
         FunctionArgs::String(token_reference) => {
-            if ctx.config().no_call_parentheses
-                && !matches!(call_next_node, FunctionCallNextNode::ObscureWithoutParens)
+            // When only formatting whitespace, parentheses are never added
+            // The length of the string is not taken into account: wrapping a long string in parentheses does not let
+            // it fit within the column width, so it is left as `foo "string"`
+            if (ctx.config().no_call_parentheses
+                && !matches!(call_next_node, FunctionCallNextNode::ObscureWithoutParens))
+                || ctx.config().whitespace_only
             {
                 let token_reference = format_token_reference(ctx, token_reference, shape)
                     .update_leading_trivia(FormatTriviaType::Append(vec![Token::new(
@@ -506,8 +627,9 @@ pub fn format_function_args(
         }
 
         FunctionArgs::TableConstructor(table_constructor) => {
-            if ctx.config().no_call_parentheses
-                && !matches!(call_next_node, FunctionCallNextNode::ObscureWithoutParens)
+            if (ctx.config().no_call_parentheses
+                && !matches!(call_next_node, FunctionCallNextNode::ObscureWithoutParens))
+                || ctx.config().whitespace_only
             {
                 let table_constructor = format_table_constructor(ctx, table_constructor, shape)
                     .update_leading_trivia(FormatTriviaType::Append(vec![Token::new(
@@ -569,7 +691,6 @@ fn should_parameters_format_multiline(
     {
         let (extra_line_length, multiline_specifier_present) = function_body
             .type_specifiers()
-            .chain(std::iter::once(function_body.return_type())) // Include optional return type
             .map(|x| {
                 x.map_or((0, false), |specifier| {
                     let formatted = format_type_specifier(ctx, specifier, shape).to_string();
@@ -593,7 +714,26 @@ fn should_parameters_format_multiline(
         }
 
         // Add the extra length
-        line_length += extra_line_length
+        line_length += extra_line_length;
+
+        // The return type follows the closing parentheses, so format it against the width remaining after the
+        // parameters. Only its first line shares the line with the parameters.
+        if let Some(return_type) = function_body.return_type() {
+            let formatted =
+                format_type_specifier(ctx, return_type, shape + line_length).to_string();
+            let multiline_return_type = formatted.lines().count() > 1;
+
+            if multiline_return_type && function_body.parameters().len() > 1 {
+                return true;
+            }
+
+            line_length += formatted.lines().next().map_or(0, str::len);
+        }
+    }
+
+    // With no parameters, expanding the parentheses cannot reduce the width of the line
+    if function_body.parameters().is_empty() {
+        return false;
     }
 
     // If the block is empty, then the `end` will be inlined. We should include this in our line length check
@@ -625,7 +765,9 @@ pub fn format_function_body(
         .generics()
         .map(|generic_declaration| format_generic_declaration(ctx, generic_declaration, shape));
     #[cfg(feature = "luau")]
-    let shape = shape + generics.as_ref().map_or(0, |x| x.to_string().len());
+    let shape = generics
+        .as_ref()
+        .map_or(shape, |generics| shape.take_last_line(generics));
 
     // Check if the parameters should be placed across multiple lines
     let multiline_params = {
@@ -650,7 +792,14 @@ pub fn format_function_body(
             contains_comments || type_specifier_comments
         });
 
+        // Comments inside of the parentheses would be lost if we formatted them onto a single line
+        let (start_parens, end_parens) = function_body.parameters_parentheses().tokens();
+        let parentheses_contain_comments =
+            trivia_util::token_contains_trailing_comments(start_parens)
+                || trivia_util::token_contains_leading_comments(end_parens);
+
         contains_comments
+            || parentheses_contain_comments
             || should_parameters_format_multiline(ctx, function_body, shape, block_empty)
     };
 
@@ -660,38 +809,36 @@ pub fn format_function_body(
             // Format start and end brace properly with correct trivia
             let (start_parens, end_parens) = function_body.parameters_parentheses().tokens();
 
-            // Calculate to see if the end parentheses requires any additional indentation
-            let end_parens_leading_trivia =
-                vec![create_newline_trivia(ctx), create_indent_trivia(ctx, shape)];
-
             // Add new_line trivia to start_parens
             let start_parens_token = fmt_symbol!(ctx, start_parens, "(", shape)
                 .update_trailing_trivia(FormatTriviaType::Append(vec![create_newline_trivia(ctx)]));
 
-            let end_parens_token = TokenReference::new(
-                end_parens_leading_trivia,
-                Token::new(TokenType::Symbol {
-                    symbol: Symbol::RightParen,
-                }),
-                vec![],
-            );
+            // The last parameter is not followed by a newline, so we need to add one before the end parentheses.
+            // Any comments leading the end parentheses are kept, indented as if they were parameters
+            let end_parens_token =
+                format_end_token(ctx, end_parens, EndTokenType::ClosingParens, shape);
+            let mut end_parens_leading_trivia = Vec::new();
+            if !function_body.parameters().is_empty() {
+                end_parens_leading_trivia.push(create_newline_trivia(ctx));
+            }
+            end_parens_leading_trivia.extend(end_parens_token.leading_trivia().cloned());
+            end_parens_leading_trivia.push(create_indent_trivia(ctx, shape));
+            let end_parens_token = end_parens_token
+                .update_leading_trivia(FormatTriviaType::Replace(end_parens_leading_trivia));
 
             (
                 format_multiline_parameters(ctx, function_body, shape),
-                ContainedSpan::new(
-                    start_parens_token,
-                    format_symbol(ctx, end_parens, &end_parens_token, shape),
-                ),
+                ContainedSpan::new(start_parens_token, end_parens_token),
             )
         }
         false => (
             format_singleline_parameters(ctx, function_body, shape),
-            format_contained_span(ctx, function_body.parameters_parentheses(), shape),
+            format_singleline_contained_span(ctx, function_body.parameters_parentheses(), shape),
         ),
     };
 
     #[cfg(feature = "luau")]
-    let type_specifiers;
+    let type_specifiers: Vec<Option<TypeSpecifier>>;
     #[cfg(feature = "luau")]
     let return_type;
     #[allow(unused_mut)]
@@ -710,8 +857,21 @@ pub fn format_function_body(
             .map(|x| x.map(|specifier| format_type_specifier(ctx, specifier, parameters_shape)))
             .collect();
 
+        // The return type is placed directly after the closing parentheses
+        let return_type_shape = if multiline_params {
+            shape.reset() + 1 // 1 = ")"
+        } else {
+            let type_specifiers_length: usize = type_specifiers
+                .iter()
+                .flatten()
+                .map(|specifier| specifier.to_string().len())
+                .sum();
+            // 2 = "(" + ")"
+            shape + formatted_parameters.to_string().len() + type_specifiers_length + 2
+        };
+
         return_type = function_body.return_type().map(|return_type| {
-            let formatted = format_type_specifier(ctx, return_type, shape);
+            let formatted = format_type_specifier(ctx, return_type, return_type_shape);
             added_trailing_trivia = true;
             let trivia = if block_empty {
                 vec![Token::new(TokenType::spaces(1))]
@@ -733,7 +893,11 @@ pub fn format_function_body(
     }
 
     let block_shape = shape.reset().increment_block_indent();
-    let block = format_block(ctx, function_body.block(), block_shape);
+    let block = format_block(
+        &ctx.enter_function_body(),
+        function_body.block(),
+        block_shape,
+    );
 
     let (end_token_leading_trivia, end_token_trailing_trivia) = (
         match block_empty {
@@ -774,17 +938,21 @@ pub fn format_function_call(
     function_call: &FunctionCall,
     shape: Shape,
 ) -> FunctionCall {
-    let formatted_prefix = format_prefix(ctx, function_call.prefix(), shape);
+    let mut formatted_prefix = format_prefix(ctx, function_call.prefix(), shape);
 
     let num_suffixes = function_call.suffixes().count();
+    let num_method_calls = function_call
+        .suffixes()
+        .filter(|x| matches!(x, Suffix::Call(Call::MethodCall(_))))
+        .count();
+    // Every call and index suffix counts as a link in the chain
+    let over_chain_limit = num_suffixes > ctx.config().max_inline_method_chain;
     let should_hang = {
-        // Hang if there is atleast more than one method call suffix
-        if function_call
-            .suffixes()
-            .filter(|x| matches!(x, Suffix::Call(Call::MethodCall(_))))
-            .count()
-            > 1
-        {
+        if over_chain_limit {
+            // The chain has more links than we allow inline, so always hang it regardless of width
+            true
+        } else if num_method_calls > 1 {
+            // Hang if there is atleast more than one method call suffix
             // Check if either a), we are surpassing the column width
             // Or b), one of the INTERNAL (not the last call) method call's arguments is multiline [function/table]
 
@@ -830,8 +998,23 @@ pub fn format_function_call(
     let mut formatted_suffixes = Vec::with_capacity(num_suffixes);
     let mut suffixes = function_call.suffixes().peekable();
     while let Some(suffix) = suffixes.next() {
-        // Only hang if this is a method call
-        let should_hang = should_hang && matches!(suffix, Suffix::Call(Call::MethodCall(_)));
+        // Comments before the name of the suffix are moved to the end of the previous segment.
+        // If the previous segment ends with a single line comment, the suffix is hung onto a new line
+        let (suffix, comments) = trivia_util::take_suffix_leading_comments(suffix);
+        let hang_after_comment = attach_comments_to_previous_segment(
+            &mut formatted_prefix,
+            &mut formatted_suffixes,
+            comments,
+        );
+
+        // Only hang if this is a method call. If the chain is over the limit, also hang before `.name` indexes,
+        // so that chains without method calls are wrapped too
+        let is_hang_point = match suffix {
+            Suffix::Call(Call::MethodCall(_)) => true,
+            Suffix::Index(Index::Dot { .. }) => over_chain_limit,
+            _ => false,
+        };
+        let should_hang = (should_hang && is_hang_point) || hang_after_comment;
         let current_shape = if should_hang {
             // Reset the shape as the call will be on a newline
             shape = shape.reset();
@@ -851,7 +1034,7 @@ pub fn format_function_call(
             FunctionCallNextNode::None
         };
 
-        let mut suffix = format_suffix(ctx, suffix, current_shape, ambiguous_next_suffix);
+        let mut suffix = format_suffix(ctx, &suffix, current_shape, ambiguous_next_suffix);
 
         if should_hang {
             suffix = suffix.update_leading_trivia(FormatTriviaType::Append(vec![
@@ -904,24 +1087,89 @@ pub fn format_function_name(
     FunctionName::new(formatted_names).with_method(formatted_method)
 }
 
+/// Removes any comments trailing the `function` keyword of a function declaration, or surrounding the tokens of its
+/// name. Returns the updated keyword and name, and the comments taken, in order.
+fn take_function_name_comments(
+    function_token: TokenReference,
+    function_name: FunctionName,
+) -> (TokenReference, FunctionName, Vec<Token>) {
+    let mut comments: Vec<Token> = function_token
+        .trailing_trivia()
+        .filter(|trivia| trivia_util::trivia_is_comment(trivia))
+        .cloned()
+        .collect();
+    // The space after the keyword is part of its trailing trivia, so it is kept
+    let space = Token::new(TokenType::spaces(1));
+    let function_token =
+        function_token.update_trailing_trivia(FormatTriviaType::Replace(vec![space]));
+
+    let mut take_comments = |token: &TokenReference| {
+        comments.extend(
+            token
+                .leading_trivia()
+                .chain(token.trailing_trivia())
+                .filter(|trivia| trivia_util::trivia_is_comment(trivia))
+                .cloned(),
+        );
+        strip_trivia(token)
+    };
+
+    let names = function_name
+        .names()
+        .pairs()
+        .map(|pair| match pair {
+            Pair::Punctuated(name, punctuation) => {
+                Pair::new(take_comments(name), Some(take_comments(punctuation)))
+            }
+            Pair::End(name) => Pair::new(take_comments(name), None),
+        })
+        .collect();
+    let method = match (function_name.method_colon(), function_name.method_name()) {
+        (Some(method_colon), Some(method_name)) => {
+            Some((take_comments(method_colon), take_comments(method_name)))
+        }
+        _ => None,
+    };
+
+    (
+        function_token,
+        FunctionName::new(names).with_method(method),
+        comments,
+    )
+}
+
+// TODO: Format Luau function attributes (e.g. `@native`) once full_moon can parse them
 /// Formats a FunctionDeclaration node
 pub fn format_function_declaration(
     ctx: &Context,
     function_declaration: &FunctionDeclaration,
     shape: Shape,
 ) -> FunctionDeclaration {
-    // Calculate trivia
-    let leading_trivia = vec![create_indent_trivia(ctx, shape)];
-
     let function_token = fmt_symbol!(
         ctx,
         function_declaration.function_token(),
         "function ",
         shape
-    )
-    .update_leading_trivia(FormatTriviaType::Append(leading_trivia));
+    );
     let formatted_function_name = format_function_name(ctx, function_declaration.name(), shape);
 
+    // The function name is never split across lines, so any comments within it, or after the `function` keyword,
+    // are moved onto their own lines before the declaration
+    let (function_token, formatted_function_name, comments) =
+        take_function_name_comments(function_token, formatted_function_name);
+
+    // Calculate trivia
+    let mut leading_trivia = Vec::new();
+    for comment in comments {
+        leading_trivia.push(create_indent_trivia(ctx, shape));
+        leading_trivia.push(comment);
+        leading_trivia.push(create_newline_trivia(ctx));
+    }
+    leading_trivia.push(create_indent_trivia(ctx, shape));
+
+    let function_token =
+        function_token.update_leading_trivia(FormatTriviaType::Append(leading_trivia));
+
     let shape = shape + (9 + strip_trivia(&formatted_function_name).to_string().len()); // 9 = "function "
     let function_body = format_function_body(ctx, function_declaration.body(), true, shape);
 
@@ -930,6 +1178,64 @@ pub fn format_function_declaration(
         .with_body(function_body)
 }
 
+/// Converts a `local f = function(...) ... end` assignment into the equivalent `local function f(...) ... end`.
+/// Returns `None` if the assignment is not of this form, or if the conversion would not be safe.
+/// The conversion is only safe if the name is not referenced within the function body, as `local function`
+/// brings the name into scope inside its own body, whilst in the assignment it would refer to an outer variable.
+/// Assignments with comments around the name, `=` or `function` tokens are also left alone so that the comments are kept.
+pub fn local_assignment_to_local_function(
+    local_assignment: &LocalAssignment,
+) -> Option<LocalFunction> {
+    if local_assignment.names().len() != 1 || local_assignment.expressions().len() != 1 {
+        return None;
+    }
+
+    #[cfg(feature = "luau")]
+    if local_assignment
+        .type_specifiers()
+        .any(|type_specifier| type_specifier.is_some())
+    {
+        return None;
+    }
+
+    let (function_token, function_body) = match local_assignment.expressions().iter().next() {
+        Some(Expression::Value {
+            value,
+            #[cfg(feature = "luau")]
+                type_assertion: None,
+        }) => match &**value {
+            Value::Function((function_token, function_body)) => (function_token, function_body),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let name = local_assignment.names().iter().next()?;
+    let equal_token = local_assignment.equal_token()?;
+    if trivia_util::token_contains_comments(name)
+        || trivia_util::token_contains_comments(equal_token)
+        || trivia_util::token_contains_comments(function_token)
+    {
+        return None;
+    }
+
+    // The name must not be referenced within the body, otherwise it would resolve to a different variable
+    let name_is_referenced = function_body.tokens().any(|token| {
+        matches!(token.token_type(), TokenType::Identifier { .. })
+            && token.token().to_string() == name.token().to_string()
+    });
+    if name_is_referenced {
+        return None;
+    }
+
+    Some(
+        LocalFunction::new(name.to_owned())
+            .with_local_token(local_assignment.local_token().to_owned())
+            .with_function_token(function_token.to_owned())
+            .with_body(function_body.to_owned()),
+    )
+}
+
 /// Formats a LocalFunction node
 pub fn format_local_function(
     ctx: &Context,
@@ -1017,6 +1323,8 @@ fn format_multiline_parameters(
             FormatTriviaType::Append(vec![create_indent_trivia(ctx, shape)]),
         );
 
+        // A separator is only kept where one was present in the input, so a `...` parameter, which must always be last,
+        // never gains a trailing separator
         let punctuation = match pair.punctuation() {
             Some(punctuation) => {
                 // Remove any trailing comments from the parameter if present
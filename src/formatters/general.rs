@@ -1,8 +1,8 @@
 use crate::{
     check_should_format,
-    context::{create_indent_trivia, create_newline_trivia, Context},
+    context::{create_indent_trivia, create_newline_trivia, line_ending_character, Context},
     formatters::{
-        trivia::{FormatTriviaType, UpdateTrailingTrivia},
+        trivia::{FormatTriviaType, UpdateLeadingTrivia, UpdateTrailingTrivia},
         trivia_util,
     },
     shape::Shape,
@@ -81,6 +81,89 @@ fn format_single_line_comment_string(comment: &str) -> &str {
     comment.trim_end()
 }
 
+/// Replaces every line ending in the given text, whether `\n` or `\r\n`, with the given line ending
+pub fn normalize_line_endings(text: &str, line_ending: &str) -> String {
+    text.split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join(line_ending)
+}
+
+/// Reindents every line of the given text after the first, so that the least indented line starts at the given
+/// indentation and all other lines keep their indentation relative to it. Lines consisting only of whitespace are
+/// emptied, apart from the final line, which is set to the given indentation so that any closing token is aligned.
+/// Any trailing whitespace on the other lines is kept, as it may be significant.
+fn reindent_lines(text: &str, indent: &str) -> String {
+    let is_indentation = |c: char| c == ' ' || c == '\t';
+    let is_blank = |line: &str| line.trim_end_matches('\r').chars().all(is_indentation);
+
+    let mut lines = text.split('\n');
+    let first_line = lines.next().unwrap_or_default();
+    let lines: Vec<&str> = lines.collect();
+
+    let common_indent = lines
+        .iter()
+        .filter(|line| !is_blank(line))
+        .map(|line| line.len() - line.trim_start_matches(is_indentation).len())
+        .min()
+        .unwrap_or(0);
+
+    let mut output = String::from(first_line);
+    for (index, line) in lines.iter().enumerate() {
+        output.push('\n');
+        if is_blank(line) {
+            if index == lines.len() - 1 {
+                output.push_str(indent);
+            }
+            output.push_str(line.trim_start_matches(is_indentation));
+        } else {
+            output.push_str(indent);
+            output.push_str(&line[common_indent..]);
+        }
+    }
+
+    output
+}
+
+/// Normalises the case of the prefix (`0x`/`0b`) and exponent marker (`e`/`p`) of a number literal.
+/// If `preserve_number_notation` is disabled, a trailing decimal point is completed and a redundant `+` is removed
+/// from the exponent. The digits themselves, and whether the number is written as an integer or a float, never change.
+/// This includes any Luau digit separators (`1_000`), which are kept exactly where they were written.
+fn format_number_notation(ctx: &Context, text: &str) -> String {
+    let (sign, number) = match text.strip_prefix('-') {
+        Some(number) => ("-", number),
+        None => ("", text),
+    };
+
+    // Hexadecimal digits may contain an `E`, so we only lowercase the prefix and any binary exponent (`P`) of a
+    // hexadecimal float, which can never be a digit
+    let prefix = number.get(..2).map(str::to_lowercase);
+    if let Some(prefix @ ("0x" | "0b")) = prefix.as_deref() {
+        return format!("{}{}{}", sign, prefix, number[2..].replace('P', "p"));
+    }
+
+    let (mantissa, exponent) = match number.find(['e', 'E']) {
+        Some(index) => (&number[..index], Some(&number[index + 1..])),
+        None => (number, None),
+    };
+
+    let mut formatted = String::from(sign) + mantissa;
+    if !ctx.config().preserve_number_notation && formatted.ends_with('.') {
+        formatted.push('0');
+    }
+
+    if let Some(exponent) = exponent {
+        let exponent = match exponent.strip_prefix('+') {
+            Some(unsigned) if !ctx.config().preserve_number_notation => unsigned,
+            _ => exponent,
+        };
+        formatted.push('e');
+        formatted.push_str(exponent);
+    }
+
+    formatted
+}
+
 /// Formats a Token Node
 /// Also returns any extra leading or trailing trivia to add for the Token node
 /// This should only ever be called from format_token_reference
@@ -94,6 +177,9 @@ fn format_token(
     let mut trailing_trivia: Option<Vec<Token>> = None;
 
     let token_type = match token.token_type() {
+        TokenType::Number { text } if ctx.config().whitespace_only => TokenType::Number {
+            text: text.to_owned(),
+        },
         TokenType::Number { text } => {
             let text = if text.starts_with('.') {
                 String::from("0") + text.as_str()
@@ -101,20 +187,39 @@ fn format_token(
                 String::from("-0") + text.get(1..).expect("unknown number literal")
             } else {
                 text.to_string()
-            }
-            .into();
+            };
 
-            TokenType::Number { text }
+            TokenType::Number {
+                text: format_number_notation(ctx, &text).into(),
+            }
         }
         TokenType::StringLiteral {
             literal,
             multi_line,
             quote_type,
         } => {
-            // If we have a brackets string, don't mess with it
-            if let StringLiteralQuoteType::Brackets = quote_type {
+            if ctx.config().whitespace_only {
+                token.token_type().to_owned()
+            } else if let StringLiteralQuoteType::Brackets = quote_type {
+                // If we have a brackets string, don't mess with it, unless we are reindenting its contents or normalising
+                // its line endings
+                let mut literal = if ctx.config().reindent_multiline_strings {
+                    let indent = create_indent_trivia(ctx, shape).to_string();
+                    reindent_lines(literal, &indent).into()
+                } else {
+                    literal.to_owned()
+                };
+
+                if ctx.config().normalize_string_line_endings {
+                    literal = normalize_line_endings(
+                        &literal,
+                        &line_ending_character(ctx.config().line_endings),
+                    )
+                    .into();
+                }
+
                 TokenType::StringLiteral {
-                    literal: literal.to_owned(),
+                    literal,
                     multi_line: *multi_line,
                     quote_type: StringLiteralQuoteType::Brackets,
                 }
@@ -178,7 +283,15 @@ fn format_token(
             }
         }
         TokenType::SingleLineComment { comment } => {
-            let comment = format_single_line_comment_string(comment).into();
+            let comment = if ctx
+                .config()
+                .verbatim_comment_prefixes()
+                .matches(&token.to_string())
+            {
+                comment.to_owned()
+            } else {
+                format_single_line_comment_string(comment).into()
+            };
 
             match format_type {
                 FormatTokenType::LeadingTrivia => {
@@ -237,13 +350,15 @@ fn load_token_trivia(
                 // If we are formatting trailing trivia, this can be ignored, as all trailing newlines will have already
                 // been handled by the formatter.
                 // If we are formatting leading trivia, we will allow a single newline to be kept in succession, if we
-                // find one.
+                // find one, or up to `max_blank_lines_in_function` within a function body. This also keeps a blank line
+                // left between a comment and the code following it, such as a file header, so that the comment does not
+                // get attached to code it was not written for.
                 match format_token_type {
                     FormatTokenType::LeadingTrivia => {
                         if characters.contains('\n') {
                             newline_count_in_succession += 1;
-                            if newline_count_in_succession == 1 {
-                                // We have a case where we will allow a single newline to be kept
+                            if newline_count_in_succession <= ctx.max_blank_lines() {
+                                // We have a case where we will allow the newline to be kept
                                 token_trivia.push(create_newline_trivia(ctx));
                             }
                         }
@@ -278,7 +393,8 @@ fn load_token_trivia(
                         }
                     }
                 }
-                // We will reset the counter as well, because the newline above is only to terminate the comment
+                // We will reset the counter as well, because the newline above is only to terminate the comment.
+                // Any further newline is a blank line after the comment, which will be kept
                 newline_count_in_succession = 0;
             }
             _ => {
@@ -516,6 +632,23 @@ pub fn format_contained_span(
     )
 }
 
+/// Formats a ContainedSpan whose contents are kept on a single line, such as the parentheses of a function call.
+/// Any newlines before the closing token are removed, as otherwise a blank line (e.g. in `foo(\n\n)`) would be kept and
+/// leave the closing token on its own line. This should only be used when there are no comments within the span.
+pub fn format_singleline_contained_span(
+    ctx: &Context,
+    contained_span: &ContainedSpan,
+    shape: Shape,
+) -> ContainedSpan {
+    let (start_token, end_token) = contained_span.tokens();
+
+    ContainedSpan::new(
+        format_token_reference(ctx, start_token, shape),
+        format_token_reference(ctx, end_token, shape)
+            .update_leading_trivia(FormatTriviaType::Replace(vec![])),
+    )
+}
+
 /// Formats a special TokenReference which is a symbol
 /// Used to preserve the comments around the symbol
 pub fn format_symbol(
@@ -569,13 +702,38 @@ pub fn format_end_token(
     shape: Shape,
 ) -> TokenReference {
     // Indent any comments leading a token, as these comments are technically part of the function body block
+    // The indent level we are currently at is one less (as we are at the block closing token, not the indented block).
+    // The comment is present inside the indented block
+    format_block_closing_token(
+        ctx,
+        current_token,
+        shape.increment_additional_indent(),
+        shape,
+    )
+}
+
+/// Formats a token which ends a block and starts another clause, such as `else`. This is the same as [`format_end_token`],
+/// except that any comments leading the token are indented at the same level as the token itself, rather than the block.
+pub fn format_clause_token(
+    ctx: &Context,
+    current_token: &TokenReference,
+    shape: Shape,
+) -> TokenReference {
+    format_block_closing_token(ctx, current_token, shape, shape)
+}
+
+/// Formats a token which ends a block, indenting any comments leading it using the given comment shape
+fn format_block_closing_token(
+    ctx: &Context,
+    current_token: &TokenReference,
+    comment_shape: Shape,
+    shape: Shape,
+) -> TokenReference {
     let formatted_leading_trivia: Vec<Token> = load_token_trivia(
         ctx,
         current_token.leading_trivia().collect(),
         FormatTokenType::LeadingTrivia,
-        // The indent level we are currently at is one less (as we are at the block closing token, not the indented block).
-        // The comment is present inside the indented block
-        shape.increment_additional_indent(),
+        comment_shape,
     );
     let formatted_trailing_trivia: Vec<Token> = load_token_trivia(
         ctx,
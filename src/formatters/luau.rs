@@ -2,10 +2,12 @@ use crate::{
     context::{create_indent_trivia, create_newline_trivia, Context},
     fmt_op, fmt_symbol,
     formatters::{
+        assignment::attempt_assignment_tactics,
         expression::{format_expression, format_var},
         general::{
-            format_contained_span, format_end_token, format_punctuated, format_symbol,
-            format_token_reference, try_format_punctuated, EndTokenType,
+            format_contained_span, format_end_token, format_punctuated,
+            format_punctuated_multiline, format_symbol, format_token_reference,
+            try_format_punctuated, EndTokenType,
         },
         table::{create_table_braces, format_multiline_table, format_singleline_table, TableType},
         trivia::{
@@ -13,8 +15,9 @@ use crate::{
             UpdateLeadingTrivia, UpdateTrailingTrivia,
         },
         trivia_util::{
-            contains_comments, token_trivia_contains_comments, trivia_is_comment,
-            trivia_is_newline, type_info_trailing_trivia,
+            contains_comments, expression_contains_inline_comments, token_contains_comments,
+            token_trivia_contains_comments, trivia_is_comment, trivia_is_newline,
+            type_info_trailing_trivia,
         },
     },
     shape::Shape,
@@ -26,6 +29,7 @@ use full_moon::ast::{
         GenericDeclarationParameter, IndexedTypeInfo, TypeArgument, TypeAssertion, TypeDeclaration,
         TypeField, TypeFieldKey, TypeInfo, TypeSpecifier,
     },
+    Expression,
 };
 use full_moon::ast::{punctuated::Punctuated, span::ContainedSpan};
 use full_moon::tokenizer::{Token, TokenReference, TokenType};
@@ -43,6 +47,20 @@ pub fn format_compound_op(ctx: &Context, compound_op: &CompoundOp, shape: Shape)
     })
 }
 
+/// Returns the compound operator with its token replaced by the given token
+fn compound_op_with_token(compound_op: &CompoundOp, token: TokenReference) -> CompoundOp {
+    match compound_op {
+        CompoundOp::PlusEqual(_) => CompoundOp::PlusEqual(token),
+        CompoundOp::MinusEqual(_) => CompoundOp::MinusEqual(token),
+        CompoundOp::StarEqual(_) => CompoundOp::StarEqual(token),
+        CompoundOp::SlashEqual(_) => CompoundOp::SlashEqual(token),
+        CompoundOp::PercentEqual(_) => CompoundOp::PercentEqual(token),
+        CompoundOp::CaretEqual(_) => CompoundOp::CaretEqual(token),
+        CompoundOp::TwoDotsEqual(_) => CompoundOp::TwoDotsEqual(token),
+        other => panic!("unknown node {:?}", other),
+    }
+}
+
 pub fn format_compound_assignment(
     ctx: &Context,
     compound_assignment: &CompoundAssignment,
@@ -52,14 +70,41 @@ pub fn format_compound_assignment(
     let leading_trivia = vec![create_indent_trivia(ctx, shape)];
     let trailing_trivia = vec![create_newline_trivia(ctx)];
 
+    // Check if the compound operator or the expression contain comments. If they do, we bail out of determining
+    // any tactics and format multiline
+    let contains_comments =
+        token_contains_comments(compound_assignment.compound_operator().token())
+            || expression_contains_inline_comments(compound_assignment.rhs());
+
     let lhs = format_var(ctx, compound_assignment.lhs(), shape)
         .update_leading_trivia(FormatTriviaType::Append(leading_trivia));
-    let compound_operator = format_compound_op(ctx, compound_assignment.compound_operator(), shape);
+    let mut compound_operator =
+        format_compound_op(ctx, compound_assignment.compound_operator(), shape);
     let shape = shape
-        + (strip_leading_trivia(&lhs).to_string().len() + compound_operator.to_string().len());
+        + (strip_leading_trivia(&lhs).to_string().len()
+            + compound_operator.token().token().to_string().len()
+            + 2); // 2 = spaces around the operator
+
+    // Firstly attempt to format the expression onto a single line, using an infinite column width shape
+    let mut rhs = format_expression(ctx, compound_assignment.rhs(), shape.with_infinite_width());
+
+    // Test the compound assignment to see if its over width. If it is, hang the expression in the same way as
+    // a normal assignment
+    if contains_comments || (shape + strip_trailing_trivia(&rhs).to_string().len()).over_budget() {
+        let expressions = std::iter::once(Pair::new(compound_assignment.rhs().to_owned(), None))
+            .collect::<Punctuated<Expression>>();
+        let (expressions, operator_token) = attempt_assignment_tactics(
+            ctx,
+            &expressions,
+            shape,
+            compound_operator.token().to_owned(),
+        );
 
-    let rhs = format_expression(ctx, compound_assignment.rhs(), shape)
-        .update_trailing_trivia(FormatTriviaType::Append(trailing_trivia));
+        rhs = expressions.into_iter().next().unwrap();
+        compound_operator = compound_op_with_token(&compound_operator, operator_token);
+    }
+
+    let rhs = rhs.update_trailing_trivia(FormatTriviaType::Append(trailing_trivia));
 
     CompoundAssignment::new(lhs, compound_operator, rhs)
 }
@@ -202,6 +247,7 @@ pub fn format_type_info(ctx: &Context, type_info: &TypeInfo, shape: Shape) -> Ty
         } => {
             let left = Box::new(format_type_info(ctx, left, shape));
             let ampersand = fmt_symbol!(ctx, ampersand, " & ", shape);
+            let shape = shape.take_last_line(&left);
             let right = Box::new(format_type_info(ctx, right, shape + 3)); // 3 = " & "
             TypeInfo::Intersection {
                 left,
@@ -259,7 +305,7 @@ pub fn format_type_info(ctx: &Context, type_info: &TypeInfo, shape: Shape) -> Ty
                         end_brace.token().start_position().bytes(),
                     );
 
-                    let singleline_shape = shape + (braces_range.1 - braces_range.0);
+                    let singleline_shape = shape + (braces_range.1 - braces_range.0) + 3; // 3 = two braces + single space before last brace
 
                     match singleline_shape.over_budget() {
                         true => TableType::MultiLine,
@@ -323,15 +369,47 @@ pub fn format_type_info(ctx: &Context, type_info: &TypeInfo, shape: Shape) -> Ty
         }
 
         TypeInfo::Tuple { parentheses, types } => {
-            let parentheses = format_contained_span(ctx, parentheses, shape);
-            let types = try_format_punctuated(ctx, types, shape + 1, format_type_info, None); // 1 = "("
+            let singleline_types =
+                try_format_punctuated(ctx, types, shape + 1, format_type_info, None); // 1 = "("
 
-            TypeInfo::Tuple { parentheses, types }
+            // If a tuple of multiple types does not fit on a single line, expand it so that each type is on its own line
+            let require_multiline =
+                types.len() > 1 && (shape.take_first_line(&singleline_types) + 2).over_budget(); // 2 = "(" + ")"
+
+            if require_multiline {
+                let (start_parens, end_parens) = parentheses.tokens();
+                let start_parens = fmt_symbol!(ctx, start_parens, "(", shape)
+                    .update_trailing_trivia(FormatTriviaType::Append(vec![create_newline_trivia(
+                        ctx,
+                    )]));
+                let end_parens =
+                    format_end_token(ctx, end_parens, EndTokenType::ClosingParens, shape)
+                        .update_leading_trivia(FormatTriviaType::Append(vec![
+                            create_newline_trivia(ctx),
+                            create_indent_trivia(ctx, shape),
+                        ]));
+                let parentheses = ContainedSpan::new(start_parens, end_parens);
+
+                let shape = shape.reset().increment_additional_indent();
+                let types = format_punctuated_multiline(ctx, types, shape, format_type_info, None)
+                    .update_leading_trivia(FormatTriviaType::Append(vec![create_indent_trivia(
+                        ctx, shape,
+                    )]));
+
+                TypeInfo::Tuple { parentheses, types }
+            } else {
+                let parentheses = format_contained_span(ctx, parentheses, shape);
+                TypeInfo::Tuple {
+                    parentheses,
+                    types: singleline_types,
+                }
+            }
         }
 
         TypeInfo::Union { left, pipe, right } => {
             let left = Box::new(format_type_info(ctx, left, shape));
             let pipe = fmt_symbol!(ctx, pipe, " | ", shape);
+            let shape = shape.take_last_line(&left);
             let right = Box::new(format_type_info(ctx, right, shape + 3)); // 3 = " | "
 
             TypeInfo::Union { left, pipe, right }
@@ -483,12 +561,22 @@ pub fn format_type_assertion(
     type_assertion: &TypeAssertion,
     shape: Shape,
 ) -> TypeAssertion {
-    let assertion_op = fmt_symbol!(ctx, type_assertion.assertion_op(), " :: ", shape);
-    let cast_to = format_type_info(ctx, type_assertion.cast_to(), shape + 4); // 4 = " :: "
+    let assertion_op_str = if ctx.config().spaces_around_type_assertion {
+        " :: "
+    } else {
+        "::"
+    };
+    let assertion_op = fmt_symbol!(ctx, type_assertion.assertion_op(), assertion_op_str, shape);
+    let cast_to = format_type_info(
+        ctx,
+        type_assertion.cast_to(),
+        shape + assertion_op_str.len(),
+    );
 
     TypeAssertion::new(cast_to).with_assertion_op(assertion_op)
 }
 
+// TODO: Format Luau type functions (`type function`) once full_moon can parse them
 fn format_type_declaration(
     ctx: &Context,
     type_declaration: &TypeDeclaration,
@@ -589,8 +677,21 @@ pub fn format_generic_declaration(
     generic_declaration: &GenericDeclaration,
     shape: Shape,
 ) -> GenericDeclaration {
+    let singleline_generics = try_format_punctuated(
+        ctx,
+        generic_declaration.generics(),
+        shape + 1, // 1 = "<"
+        format_generic_parameter,
+        None,
+    );
+
     // If the generics contains comments, then format multiline
-    let (arrows, generics) = if contains_comments(generic_declaration.generics()) {
+    let contains_comments = contains_comments(generic_declaration.generics());
+    // If there are multiple generics which do not fit on a single line, then place each one on its own line
+    let require_multiline = generic_declaration.generics().len() > 1
+        && (shape.take_first_line(&singleline_generics) + 3).over_budget(); // 3 = "<" + ">" + "(" or " "
+
+    let (arrows, generics) = if contains_comments || require_multiline {
         let (start_arrow, end_arrow) = generic_declaration.arrows().tokens();
 
         // Format start and end arrows properly with correct trivia
@@ -607,13 +708,23 @@ pub fn format_generic_declaration(
         let arrows = ContainedSpan::new(start_arrow_token, end_arrow_token);
 
         let shape = shape.reset().increment_additional_indent();
-        let generics = try_format_punctuated(
-            ctx,
-            generic_declaration.generics(),
-            shape,
-            format_generic_parameter,
-            None,
-        )
+        let generics = if contains_comments {
+            try_format_punctuated(
+                ctx,
+                generic_declaration.generics(),
+                shape,
+                format_generic_parameter,
+                None,
+            )
+        } else {
+            format_punctuated_multiline(
+                ctx,
+                generic_declaration.generics(),
+                shape,
+                format_generic_parameter,
+                None,
+            )
+        }
         .update_leading_trivia(FormatTriviaType::Append(vec![create_indent_trivia(
             ctx, shape,
         )]));
@@ -622,13 +733,7 @@ pub fn format_generic_declaration(
     } else {
         (
             format_contained_span(ctx, generic_declaration.arrows(), shape),
-            try_format_punctuated(
-                ctx,
-                generic_declaration.generics(),
-                shape,
-                format_generic_parameter,
-                None,
-            ),
+            singleline_generics,
         )
     };
 
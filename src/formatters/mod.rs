@@ -1,5 +1,6 @@
-use crate::{context::Context, shape::Shape, Config};
+use crate::{context::Context, shape::Shape, AbortSignal, Config};
 use full_moon::ast::Ast;
+use std::cell::RefCell;
 
 pub mod assignment;
 pub mod block;
@@ -19,6 +20,23 @@ pub mod trivia_util;
 use block::format_block;
 use general::format_eof;
 
+thread_local! {
+    /// The signal used to cancel the formatting run on this thread, and whether it has been triggered
+    static ABORT_SIGNAL: RefCell<(AbortSignal, bool)> = RefCell::new((AbortSignal::default(), false));
+}
+
+/// Whether the current formatting run has been aborted. Once this returns `true`, it continues to do so until the end
+/// of the run, so that the formatters can return early without any further work
+pub fn should_abort() -> bool {
+    ABORT_SIGNAL.with(|signal| {
+        let mut signal = signal.borrow_mut();
+        if !signal.1 && signal.0.is_aborted() {
+            signal.1 = true;
+        }
+        signal.1
+    })
+}
+
 pub struct CodeFormatter {
     /// The formatting context
     context: Context,
@@ -34,10 +52,28 @@ impl CodeFormatter {
 
     /// Runs the formatter over the given AST
     pub fn format(&self, ast: Ast) -> Ast {
+        table::clear_table_constructor_cache();
+        trivia_util::compute_nesting_depths(ast.nodes());
+
         let shape = Shape::new(&self.context);
         let new_block = format_block(&self.context, ast.nodes(), shape);
         let new_eof = format_eof(&self.context, ast.eof(), shape);
+        table::clear_table_constructor_cache();
+        trivia_util::clear_nesting_depths();
 
         ast.with_nodes(new_block).with_eof(new_eof)
     }
+
+    /// Runs the formatter over the given AST, returning `None` if the signal is triggered before it is complete
+    pub fn format_with_abort_signal(&self, ast: Ast, abort_signal: &AbortSignal) -> Option<Ast> {
+        ABORT_SIGNAL.with(|signal| *signal.borrow_mut() = (abort_signal.to_owned(), false));
+        let ast = self.format(ast);
+        let (_, aborted) =
+            ABORT_SIGNAL.with(|signal| signal.replace((AbortSignal::default(), false)));
+
+        match aborted {
+            true => None,
+            false => Some(ast),
+        }
+    }
 }
@@ -11,19 +11,26 @@ use crate::{
     formatters::{
         assignment::{format_assignment, format_local_assignment},
         block::format_block,
-        expression::{format_expression, hang_expression_trailing_newline},
-        functions::{format_function_call, format_function_declaration, format_local_function},
+        expression::{format_expression, hang_expression, hang_expression_trailing_newline},
+        functions::{
+            format_function_call, format_function_declaration, format_local_function,
+            local_assignment_to_local_function,
+        },
         general::{
-            format_end_token, format_punctuated_buffer, format_token_reference, EndTokenType,
+            format_clause_token, format_end_token, format_punctuated, format_punctuated_buffer,
+            format_token_reference, EndTokenType,
         },
         trivia::{
             strip_trivia, FormatTriviaType, UpdateLeadingTrivia, UpdateTrailingTrivia, UpdateTrivia,
         },
         trivia_util,
     },
-    shape::Shape,
+    shape::{Indent, Shape},
 };
+#[cfg(feature = "luau")]
+use full_moon::ast::types::TypeSpecifier;
 use full_moon::ast::{
+    punctuated::{Pair, Punctuated},
     Do, ElseIf, Expression, FunctionCall, GenericFor, If, NumericFor, Repeat, Stmt, Value, While,
 };
 use full_moon::tokenizer::{Token, TokenReference, TokenType};
@@ -42,19 +49,110 @@ macro_rules! fmt_stmt {
 
 /// Removes parentheses around a condition, if present.
 /// Called only for condition expression (if ... then, while ... do, etc.)
-fn remove_condition_parentheses(expression: Expression) -> Expression {
+/// The parentheses are kept if the `preserve_condition_parentheses` option is set.
+fn remove_condition_parentheses(ctx: &Context, expression: Expression) -> Expression {
+    if ctx.config().whitespace_only || ctx.config().preserve_condition_parentheses {
+        return expression;
+    }
+
     match expression.to_owned() {
         Expression::Parentheses { expression, .. } => *expression,
         Expression::Value { value, .. } => match *value {
-            Value::ParenthesesExpression(expression) => remove_condition_parentheses(expression),
+            Value::ParenthesesExpression(expression) => {
+                remove_condition_parentheses(ctx, expression)
+            }
             _ => expression,
         },
         _ => expression,
     }
 }
 
+/// Whether a statement declares a local, such as a variable or type, which is scoped to the block containing it
+fn declares_local(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::LocalAssignment(_) | Stmt::LocalFunction(_) => true,
+        #[cfg(feature = "luau")]
+        Stmt::TypeDeclaration(_) => true,
+        _ => false,
+    }
+}
+
+/// Attempts to format a trivial Do node onto a single line, i.e. `do return x end`.
+/// Only applies if the block contains a single statement with no semicolon, which does not declare a local, there are
+/// no comments within the block, and the whole statement fits on one line. Returns `None` otherwise.
+/// A block declaring a local is used to limit its scope, so it is kept as a block to make this clear.
+fn format_do_block_inline(ctx: &Context, do_block: &Do, shape: Shape) -> Option<Do> {
+    let block = do_block.block();
+    let has_single_stmt = match (
+        block.stmts_with_semicolon().count(),
+        block.last_stmt_with_semicolon(),
+    ) {
+        (1, None) => {
+            matches!(block.stmts_with_semicolon().next(), Some((stmt, None)) if !declares_local(stmt))
+        }
+        (0, Some((_, None))) => true,
+        _ => false,
+    };
+
+    if !has_single_stmt
+        || trivia_util::token_contains_trailing_comments(do_block.do_token())
+        || trivia_util::contains_comments(block)
+        || trivia_util::token_contains_leading_comments(do_block.end_token())
+    {
+        return None;
+    }
+
+    // Format the inner statement with no indentation, then remove the trailing newline it was given
+    let block_shape = shape.reset().with_indent(Indent::new(ctx));
+    let block = format_block(ctx, block, block_shape);
+    let block = match block.last_stmt() {
+        Some(last_stmt) => {
+            let (last_stmt, _) = trivia_util::get_last_stmt_trailing_trivia(last_stmt.to_owned());
+            block.with_last_stmt(Some((last_stmt, None)))
+        }
+        None => {
+            let stmt = block
+                .stmts()
+                .next()
+                .expect("no statement in block")
+                .to_owned();
+            let (stmt, _) = trivia_util::get_stmt_trailing_trivia(stmt);
+            block.with_stmts(vec![(stmt, None)])
+        }
+    };
+
+    let block_string = block.to_string();
+    if block_string.contains('\n')
+        || shape
+            .add_width("do ".len() + block_string.len() + " end".len())
+            .over_budget()
+    {
+        return None;
+    }
+
+    let do_token = fmt_symbol!(ctx, do_block.do_token(), "do ", shape).update_leading_trivia(
+        FormatTriviaType::Append(vec![create_indent_trivia(ctx, shape)]),
+    );
+    let end_token = fmt_symbol!(ctx, do_block.end_token(), " end", shape)
+        .update_trailing_trivia(FormatTriviaType::Append(vec![create_newline_trivia(ctx)]));
+
+    Some(
+        do_block
+            .to_owned()
+            .with_do_token(do_token)
+            .with_block(block)
+            .with_end_token(end_token),
+    )
+}
+
 /// Format a Do node
 pub fn format_do_block(ctx: &Context, do_block: &Do, shape: Shape) -> Do {
+    if ctx.config().collapse_simple_statement {
+        if let Some(do_block) = format_do_block_inline(ctx, do_block, shape) {
+            return do_block;
+        }
+    }
+
     // Create trivia
     let leading_trivia = FormatTriviaType::Append(vec![create_indent_trivia(ctx, shape)]);
     let trailing_trivia = FormatTriviaType::Append(vec![create_newline_trivia(ctx)]);
@@ -73,6 +171,25 @@ pub fn format_do_block(ctx: &Context, do_block: &Do, shape: Shape) -> Do {
         .with_end_token(end_token)
 }
 
+/// Places each item of a formatted punctuated list onto its own line, preceded by the given trivia.
+/// Assumes that the list contains no comments.
+fn hang_punctuated_list<T>(list: Punctuated<T>, hang_trivia: &[Token]) -> Punctuated<T>
+where
+    T: UpdateLeadingTrivia,
+{
+    list.into_pairs()
+        .map(|pair| match pair {
+            Pair::Punctuated(value, _) => Pair::Punctuated(
+                value.update_leading_trivia(FormatTriviaType::Append(hang_trivia.to_vec())),
+                TokenReference::symbol(",").unwrap(),
+            ),
+            Pair::End(value) => Pair::End(
+                value.update_leading_trivia(FormatTriviaType::Append(hang_trivia.to_vec())),
+            ),
+        })
+        .collect()
+}
+
 /// Format a GenericFor node
 pub fn format_generic_for(ctx: &Context, generic_for: &GenericFor, shape: Shape) -> GenericFor {
     // Create trivia
@@ -86,7 +203,7 @@ pub fn format_generic_for(ctx: &Context, generic_for: &GenericFor, shape: Shape)
         format_punctuated_buffer(ctx, generic_for.names(), shape, format_token_reference);
 
     #[cfg(feature = "luau")]
-    let type_specifiers = generic_for
+    let type_specifiers: Vec<Option<TypeSpecifier>> = generic_for
         .type_specifiers()
         .map(|x| x.map(|type_specifier| format_type_specifier(ctx, type_specifier, shape)))
         .collect();
@@ -95,13 +212,90 @@ pub fn format_generic_for(ctx: &Context, generic_for: &GenericFor, shape: Shape)
     let (formatted_expr_list, mut expr_comments_buf) =
         format_punctuated_buffer(ctx, generic_for.expressions(), shape, format_expression);
 
-    // Create comments buffer and append to end of do token
-    names_comments_buf.append(&mut expr_comments_buf);
-    // Append trailing trivia to the end
-    names_comments_buf.append(&mut trailing_trivia);
+    #[cfg(feature = "luau")]
+    let type_specifiers_width: usize = type_specifiers
+        .iter()
+        .flatten()
+        .map(|type_specifier| type_specifier.to_string().len())
+        .sum();
+    #[cfg(not(feature = "luau"))]
+    let type_specifiers_width = 0;
+
+    let singleline_shape = shape
+        + (4 // 4 = "for "
+            + formatted_names.to_string().len()
+            + type_specifiers_width
+            + 4 // 4 = " in "
+            + formatted_expr_list.to_string().len()
+            + 3); // 3 = " do"
+
+    // If the header does not fit on a single line, hang the names (and if necessary, the expressions) one per line
+    let (for_token, formatted_names, in_token, formatted_expr_list, do_token) = if singleline_shape
+        .over_budget()
+        && !trivia_util::contains_comments(generic_for.names())
+        && !trivia_util::contains_comments(generic_for.expressions())
+    {
+        let hang_shape = shape.reset().increment_additional_indent();
+        let hang_trivia = vec![
+            create_newline_trivia(ctx),
+            create_indent_trivia(ctx, hang_shape),
+        ];
+        let line_trivia = vec![create_newline_trivia(ctx), create_indent_trivia(ctx, shape)];
+
+        let for_token = fmt_symbol!(ctx, generic_for.for_token(), "for", shape)
+            .update_leading_trivia(FormatTriviaType::Append(leading_trivia.to_owned()));
+        let formatted_names = hang_punctuated_list(formatted_names, &hang_trivia);
+
+        // Keep the expressions on the `in` line if they fit, otherwise hang them one per line as well
+        let in_shape = shape.reset() + 3; // 3 = "in "
+        let (in_token, formatted_expr_list, do_token) = if (in_shape
+            + (formatted_expr_list.to_string().len() + 3)) // 3 = " do"
+            .over_budget()
+        {
+            let formatted_expr_list = format_punctuated(
+                ctx,
+                generic_for.expressions(),
+                hang_shape,
+                format_expression,
+            );
+            (
+                fmt_symbol!(ctx, generic_for.in_token(), "in", shape),
+                hang_punctuated_list(formatted_expr_list, &hang_trivia),
+                fmt_symbol!(ctx, generic_for.do_token(), "do", shape)
+                    .update_leading_trivia(FormatTriviaType::Append(line_trivia.to_owned())),
+            )
+        } else {
+            (
+                fmt_symbol!(ctx, generic_for.in_token(), "in ", shape),
+                format_punctuated(ctx, generic_for.expressions(), in_shape, format_expression),
+                fmt_symbol!(ctx, generic_for.do_token(), " do", shape),
+            )
+        };
 
-    let do_token = fmt_symbol!(ctx, generic_for.do_token(), " do", shape)
-        .update_trailing_trivia(FormatTriviaType::Append(names_comments_buf));
+        (
+            for_token,
+            formatted_names,
+            in_token.update_leading_trivia(FormatTriviaType::Append(line_trivia)),
+            formatted_expr_list,
+            do_token.update_trailing_trivia(FormatTriviaType::Append(trailing_trivia)),
+        )
+    } else {
+        // Create comments buffer and append to end of do token
+        names_comments_buf.append(&mut expr_comments_buf);
+        // Append trailing trivia to the end
+        names_comments_buf.append(&mut trailing_trivia);
+
+        let do_token = fmt_symbol!(ctx, generic_for.do_token(), " do", shape)
+            .update_trailing_trivia(FormatTriviaType::Append(names_comments_buf));
+
+        (
+            for_token,
+            formatted_names,
+            in_token,
+            formatted_expr_list,
+            do_token,
+        )
+    };
 
     let block_shape = shape.reset().increment_block_indent();
     let block = format_block(ctx, generic_for.block(), block_shape);
@@ -125,27 +319,95 @@ pub fn format_generic_for(ctx: &Context, generic_for: &GenericFor, shape: Shape)
         .with_end_token(end_token)
 }
 
+/// Returns the shape to use for an `else` or `elseif` clause, given the shape of the `if` statement it is part of.
+/// Depending on configuration, the clause is either aligned with the `if`, or indented one level deeper.
+fn else_clause_shape(ctx: &Context, shape: Shape) -> Shape {
+    if ctx.config().indent_else_clauses {
+        shape.increment_block_indent()
+    } else {
+        shape
+    }
+}
+
+/// Takes the comments trailing the condition of an `if`, `elseif` or `while` statement, so that they can be placed after
+/// the following `then` or `do` keyword instead. This is only done if enabled in the configuration, and if there are no
+/// other comments within the condition or around the keyword, which would still require the condition to be multiline.
+fn take_condition_trailing_comments(
+    ctx: &Context,
+    condition: Expression,
+    keyword: &TokenReference,
+) -> (Expression, Vec<Token>) {
+    if !ctx.config().inline_condition_trailing_comments || trivia_util::contains_comments(keyword) {
+        return (condition, Vec::new());
+    }
+
+    let (stripped_condition, comments) = trivia_util::take_expression_trailing_comments(&condition);
+    if trivia_util::contains_comments(&stripped_condition) {
+        (condition, Vec::new())
+    } else {
+        (stripped_condition, comments)
+    }
+}
+
+/// Returns whether the `then` or `do` keyword following a hung condition should be attached to the last line of the
+/// condition. The keyword is kept on its own line if there are any comments which would end up before it.
+fn attach_hanging_condition_keyword(
+    ctx: &Context,
+    condition: &Expression,
+    keyword: &TokenReference,
+) -> bool {
+    ctx.config().attach_hanging_condition_keyword
+        && !trivia_util::contains_comments(condition)
+        && !trivia_util::token_contains_leading_comments(keyword)
+}
+
+/// Hangs the condition of an `if`, `elseif` or `while` statement onto its own lines, indented one level deeper.
+/// A trailing newline is added unless the following keyword is attached to the condition.
+fn hang_condition(
+    ctx: &Context,
+    condition: &Expression,
+    attach_keyword: bool,
+    shape: Shape,
+) -> Expression {
+    let shape = shape.reset().increment_additional_indent();
+    let condition = match attach_keyword {
+        true => hang_expression(ctx, condition, shape, None),
+        false => hang_expression_trailing_newline(ctx, condition, shape, None),
+    };
+    condition.update_leading_trivia(FormatTriviaType::Append(vec![create_indent_trivia(
+        ctx, shape,
+    )]))
+}
+
 /// Formats an ElseIf node - This must always reside within format_if
 fn format_else_if(ctx: &Context, else_if_node: &ElseIf, shape: Shape) -> ElseIf {
     // Calculate trivia
-    let shape = shape.reset();
+    let if_shape = shape.reset();
+    let shape = else_clause_shape(ctx, if_shape);
     let leading_trivia = vec![create_indent_trivia(ctx, shape)];
     let trailing_trivia = vec![create_newline_trivia(ctx)];
 
     // Remove parentheses around the condition
-    let condition = remove_condition_parentheses(else_if_node.condition().to_owned());
-
-    let elseif_token = format_end_token(
-        ctx,
-        else_if_node.else_if_token(),
-        EndTokenType::BlockEnd,
-        shape,
-    );
-    let singleline_condition = format_expression(ctx, &condition, shape + 7);
+    let condition = remove_condition_parentheses(ctx, else_if_node.condition().to_owned());
+    let (condition, condition_comments) =
+        take_condition_trailing_comments(ctx, condition, else_if_node.then_token());
+
+    // Any comments leading the `elseif` token are part of the previous block, so are indented relative to the `if`,
+    // unless they are configured to be aligned with the `elseif` itself
+    let elseif_token = match ctx.config().align_else_leading_comments {
+        true => format_clause_token(ctx, else_if_node.else_if_token(), shape),
+        false => format_end_token(
+            ctx,
+            else_if_node.else_if_token(),
+            EndTokenType::BlockEnd,
+            if_shape,
+        ),
+    };
+    let singleline_condition = format_expression(ctx, &condition, shape + 7); // 7 = "elseif "
     let singleline_then_token = fmt_symbol!(ctx, else_if_node.then_token(), " then", shape);
 
     // Determine if we need to hang the condition
-    let singleline_shape = shape + (7 + 5 + strip_trivia(&singleline_condition).to_string().len()); // 7 = "elseif ", 3 = " then"
+    let singleline_shape = shape + (7 + 5 + strip_trivia(&singleline_condition).to_string().len()); // 7 = "elseif ", 5 = " then"
     let require_multiline_expression = singleline_shape.over_budget()
         || trivia_util::token_contains_trailing_comments(else_if_node.else_if_token())
         || trivia_util::token_contains_leading_comments(else_if_node.then_token())
@@ -160,17 +422,15 @@ fn format_else_if(ctx: &Context, else_if_node: &ElseIf, shape: Shape) -> ElseIf
     }
     .update_leading_trivia(FormatTriviaType::Append(leading_trivia.to_owned()));
 
+    let attach_keyword = require_multiline_expression
+        && attach_hanging_condition_keyword(ctx, &condition, else_if_node.then_token());
+
     let condition = match require_multiline_expression {
-        true => {
-            let shape = shape.reset().increment_additional_indent();
-            hang_expression_trailing_newline(ctx, &condition, shape, None).update_leading_trivia(
-                FormatTriviaType::Append(vec![create_indent_trivia(ctx, shape)]),
-            )
-        }
+        true => hang_condition(ctx, &condition, attach_keyword, shape),
         false => singleline_condition,
     };
 
-    let then_token = match require_multiline_expression {
+    let then_token = match require_multiline_expression && !attach_keyword {
         true => format_end_token(
             ctx,
             else_if_node.then_token(),
@@ -180,6 +440,7 @@ fn format_else_if(ctx: &Context, else_if_node: &ElseIf, shape: Shape) -> ElseIf
         .update_leading_trivia(FormatTriviaType::Append(leading_trivia)),
         false => singleline_then_token,
     }
+    .update_trailing_trivia(FormatTriviaType::Append(condition_comments))
     .update_trailing_trivia(FormatTriviaType::Append(trailing_trivia));
 
     let block_shape = shape.reset().increment_block_indent();
@@ -200,10 +461,12 @@ pub fn format_if(ctx: &Context, if_node: &If, shape: Shape) -> If {
     let trailing_trivia = vec![create_newline_trivia(ctx)];
 
     // Remove parentheses around the condition
-    let condition = remove_condition_parentheses(if_node.condition().to_owned());
+    let condition = remove_condition_parentheses(ctx, if_node.condition().to_owned());
+    let (condition, condition_comments) =
+        take_condition_trailing_comments(ctx, condition, if_node.then_token());
 
     let singleline_if_token = fmt_symbol!(ctx, if_node.if_token(), "if ", shape);
-    let singleline_condition = format_expression(ctx, &condition, shape + 6);
+    let singleline_condition = format_expression(ctx, &condition, shape + 3); // 3 = "if "
     let singleline_then_token = fmt_symbol!(ctx, if_node.then_token(), " then", shape);
 
     // Determine if we need to hang the condition
@@ -220,21 +483,20 @@ pub fn format_if(ctx: &Context, if_node: &If, shape: Shape) -> If {
     }
     .update_leading_trivia(FormatTriviaType::Append(leading_trivia.to_owned()));
 
+    let attach_keyword = require_multiline_expression
+        && attach_hanging_condition_keyword(ctx, &condition, if_node.then_token());
+
     let condition = match require_multiline_expression {
-        true => {
-            let shape = shape.reset().increment_additional_indent();
-            hang_expression_trailing_newline(ctx, &condition, shape, None).update_leading_trivia(
-                FormatTriviaType::Append(vec![create_indent_trivia(ctx, shape)]),
-            )
-        }
+        true => hang_condition(ctx, &condition, attach_keyword, shape),
         false => singleline_condition,
     };
 
-    let then_token = match require_multiline_expression {
+    let then_token = match require_multiline_expression && !attach_keyword {
         true => format_end_token(ctx, if_node.then_token(), EndTokenType::BlockEnd, shape)
             .update_leading_trivia(FormatTriviaType::Append(leading_trivia.to_owned())),
         false => singleline_then_token,
     }
+    .update_trailing_trivia(FormatTriviaType::Append(condition_comments))
     .update_trailing_trivia(FormatTriviaType::Append(trailing_trivia.to_owned()));
 
     let block_shape = shape.reset().increment_block_indent();
@@ -255,12 +517,16 @@ pub fn format_if(ctx: &Context, if_node: &If, shape: Shape) -> If {
 
     let (else_token, else_block) = match (if_node.else_token(), if_node.else_block()) {
         (Some(else_token), Some(else_block)) => {
-            let else_token = format_end_token(ctx, else_token, EndTokenType::BlockEnd, shape)
-                .update_trivia(
-                    FormatTriviaType::Append(leading_trivia),
-                    FormatTriviaType::Append(trailing_trivia),
-                );
-            let else_block_shape = shape.reset().increment_block_indent();
+            let else_shape = else_clause_shape(ctx, shape);
+            let else_token = match ctx.config().align_else_leading_comments {
+                true => format_clause_token(ctx, else_token, else_shape),
+                false => format_end_token(ctx, else_token, EndTokenType::BlockEnd, shape),
+            }
+            .update_trivia(
+                FormatTriviaType::Append(vec![create_indent_trivia(ctx, else_shape)]),
+                FormatTriviaType::Append(trailing_trivia),
+            );
+            let else_block_shape = else_shape.reset().increment_block_indent();
             let else_block = format_block(ctx, else_block, else_block_shape);
 
             (Some(else_token), Some(else_block))
@@ -352,25 +618,52 @@ pub fn format_repeat_block(ctx: &Context, repeat_block: &Repeat, shape: Shape) -
         );
     let block_shape = shape.reset().increment_block_indent();
     let block = format_block(ctx, repeat_block.block(), block_shape);
-    let until_token = fmt_symbol!(ctx, repeat_block.until_token(), "until ", shape)
-        .update_leading_trivia(FormatTriviaType::Append(leading_trivia));
+
+    // If there is a comment after the `until` token, the condition must start on the next line, indented one level
+    // from the `until` token in the same way as a hanging `while` condition
+    let condition_on_new_line =
+        trivia_util::token_contains_trailing_comments(repeat_block.until_token());
+
+    // The `until` token closes the block, so any comments before it are indented at the block level
+    let until_token = format_end_token(
+        ctx,
+        repeat_block.until_token(),
+        EndTokenType::BlockEnd,
+        shape,
+    );
+    let until_token = match condition_on_new_line {
+        true => until_token
+            .update_trailing_trivia(FormatTriviaType::Append(vec![create_newline_trivia(ctx)])),
+        false => until_token.update_trailing_trivia(FormatTriviaType::Append(vec![Token::new(
+            TokenType::spaces(1),
+        )])),
+    }
+    .update_leading_trivia(FormatTriviaType::Append(leading_trivia));
 
     // Remove parentheses around the condition
-    let condition = remove_condition_parentheses(repeat_block.until().to_owned());
+    let condition = remove_condition_parentheses(ctx, repeat_block.until().to_owned());
 
     // Determine if we need to hang the condition
     let singleline_shape = shape + (6 + strip_trivia(&condition).to_string().len()); // 6 = "until "
     let require_multiline_expression = singleline_shape.over_budget()
         || trivia_util::expression_contains_inline_comments(&condition);
 
-    let shape = shape + 6; // 6 = "until "
-    let until = match require_multiline_expression {
-        true => {
-            let shape = shape.increment_additional_indent();
-            hang_expression_trailing_newline(ctx, &condition, shape, None)
+    let until = if condition_on_new_line {
+        let shape = shape.reset().increment_additional_indent();
+        hang_expression_trailing_newline(ctx, &condition, shape, None).update_leading_trivia(
+            FormatTriviaType::Append(vec![create_indent_trivia(ctx, shape)]),
+        )
+    } else {
+        // Any hanging lines of the condition are indented one level from the `until` token
+        let shape = shape + 6; // 6 = "until "
+        match require_multiline_expression {
+            true => {
+                let shape = shape.increment_additional_indent();
+                hang_expression_trailing_newline(ctx, &condition, shape, None)
+            }
+            false => format_expression(ctx, &condition, shape)
+                .update_trailing_trivia(FormatTriviaType::Append(trailing_trivia)),
         }
-        false => format_expression(ctx, &condition, shape)
-            .update_trailing_trivia(FormatTriviaType::Append(trailing_trivia)),
     };
 
     repeat_block
@@ -388,7 +681,9 @@ pub fn format_while_block(ctx: &Context, while_block: &While, shape: Shape) -> W
     let trailing_trivia = vec![create_newline_trivia(ctx)];
 
     // Remove parentheses around the condition
-    let condition = remove_condition_parentheses(while_block.condition().to_owned());
+    let condition = remove_condition_parentheses(ctx, while_block.condition().to_owned());
+    let (condition, condition_comments) =
+        take_condition_trailing_comments(ctx, condition, while_block.do_token());
 
     let singleline_while_token = fmt_symbol!(ctx, while_block.while_token(), "while ", shape);
     let singleline_condition = format_expression(ctx, &condition, shape + 6);
@@ -408,21 +703,20 @@ pub fn format_while_block(ctx: &Context, while_block: &While, shape: Shape) -> W
     }
     .update_leading_trivia(FormatTriviaType::Append(leading_trivia.to_owned()));
 
+    let attach_keyword = require_multiline_expression
+        && attach_hanging_condition_keyword(ctx, &condition, while_block.do_token());
+
     let condition = match require_multiline_expression {
-        true => {
-            let shape = shape.reset().increment_additional_indent();
-            hang_expression_trailing_newline(ctx, &condition, shape, None).update_leading_trivia(
-                FormatTriviaType::Append(vec![create_indent_trivia(ctx, shape)]),
-            )
-        }
+        true => hang_condition(ctx, &condition, attach_keyword, shape),
         false => singleline_condition,
     };
 
-    let do_token = match require_multiline_expression {
+    let do_token = match require_multiline_expression && !attach_keyword {
         true => format_end_token(ctx, while_block.do_token(), EndTokenType::BlockEnd, shape)
             .update_leading_trivia(FormatTriviaType::Append(leading_trivia.to_owned())),
         false => singleline_do_token,
     }
+    .update_trailing_trivia(FormatTriviaType::Append(condition_comments))
     .update_trailing_trivia(FormatTriviaType::Append(trailing_trivia.to_owned()));
 
     let block_shape = shape.reset().increment_block_indent();
@@ -749,6 +1043,15 @@ pub fn format_stmt(ctx: &Context, stmt: &Stmt, shape: Shape) -> Stmt {
         return stmt_block::format_stmt_block(ctx, stmt, shape);
     }
 
+    // Rewrite `local f = function() end` as `local function f() end` if enabled and safe to do so
+    if ctx.config().prefer_local_function && !ctx.config().whitespace_only {
+        if let Stmt::LocalAssignment(local_assignment) = stmt {
+            if let Some(local_function) = local_assignment_to_local_function(local_assignment) {
+                return Stmt::LocalFunction(format_local_function(ctx, &local_function, shape));
+            }
+        }
+    }
+
     fmt_stmt!(ctx, stmt, shape, {
         Assignment = format_assignment,
         Do = format_do_block,
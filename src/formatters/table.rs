@@ -1,5 +1,8 @@
 use crate::{
-    context::{create_indent_trivia, create_newline_trivia, table_sep_character, Context},
+    context::{
+        create_indent_trivia, create_newline_trivia, table_sep_character, Context, ContextState,
+        LayoutDirective,
+    },
     fmt_symbol,
     formatters::{
         expression::{format_expression, hang_expression, is_brackets_string},
@@ -16,7 +19,12 @@ use full_moon::{
         Expression, Field, TableConstructor, Value,
     },
     node::Node,
-    tokenizer::{Token, TokenReference, TokenType},
+    tokenizer::{Symbol, Token, TokenReference, TokenType},
+};
+use std::{
+    cell::RefCell,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
 };
 
 /// Used to provide information about the table
@@ -30,6 +38,31 @@ pub enum TableType {
     Empty,
 }
 
+/// Identifies a table constructor formatted at a given shape, so that its formatted output can be reused
+#[derive(PartialEq, Eq, Hash)]
+struct TableCacheKey {
+    /// The byte positions of the start and end braces in the input
+    braces_range: (usize, usize),
+    /// A hash of the unformatted table constructor, including its trivia
+    source_hash: u64,
+    shape: Shape,
+    /// The state of the context, such as any layout directive, which may also change the output
+    context_state: ContextState,
+}
+
+thread_local! {
+    /// Formatted table constructors seen during the current formatting run.
+    /// Function arguments are formatted more than once to decide their layout, so without this a table
+    /// nested within calls would be formatted an exponential number of times with respect to its depth.
+    static TABLE_CONSTRUCTOR_CACHE: RefCell<HashMap<TableCacheKey, TableConstructor>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Clears any formatted table constructors memoized during a previous formatting run
+pub fn clear_table_constructor_cache() {
+    TABLE_CONSTRUCTOR_CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
 /// Formats an Expression value part of a k,v field pair
 fn format_field_expression_value(
     ctx: &Context,
@@ -294,11 +327,9 @@ where
     let mut current_fields = fields.pairs().peekable();
     let mut fields = Punctuated::new();
 
-    let mut symbol = table_sep_character(ctx.config().table_sep);
-    symbol.push_str(" ");
-
     while let Some(pair) = current_fields.next() {
         let (field, punctuation) = (pair.value(), pair.punctuation());
+        let mut symbol = table_separator(ctx, punctuation);
 
         // Format the field. We will ignore the taken trailing trivia, as we do not need it.
         // (If there were any comments present, this function should never have been called)
@@ -308,11 +339,17 @@ where
             Some(_) => {
                 // Have more elements still to go
                 shape = shape + (formatted_field.to_string().len() + 2); // 2 = ", "
+                symbol.push(' ');
                 match punctuation {
                     Some(punctuation) => Some(fmt_symbol!(ctx, punctuation, &symbol, shape)),
                     None => Some(TokenReference::symbol(&symbol).unwrap()),
                 }
             }
+            // When only formatting whitespace, keep any existing trailing separator
+            None if ctx.config().whitespace_only => {
+                shape = shape + (formatted_field.to_string().len() + 1); // 1 = ","
+                punctuation.map(|punctuation| fmt_symbol!(ctx, punctuation, &symbol, shape))
+            }
             None if ctx.config().extra_sep_at_table_end => {
                 shape = shape + (formatted_field.to_string().len() + 1); // 1 = ","
                 Some(TokenReference::symbol(&table_sep_character(ctx.config().table_sep)).unwrap())
@@ -326,6 +363,15 @@ where
     (braces, fields)
 }
 
+/// Returns the separator to place after a table field. When only formatting whitespace, the field's original
+/// separator is kept, otherwise the configured separator is used.
+fn table_separator(ctx: &Context, punctuation: Option<&TokenReference>) -> String {
+    match punctuation {
+        Some(punctuation) if ctx.config().whitespace_only => punctuation.token().to_string(),
+        _ => table_sep_character(ctx.config().table_sep),
+    }
+}
+
 /// Expands a table's fields to format it onto multiple lines
 /// Takes in a [`ContainedSpan`] representing the braces, and the fields within the table.
 /// This function is generic to support [`TableConstructor`] and [`TypeInfo::Table`] in Luau.
@@ -347,10 +393,20 @@ where
     let braces = create_table_braces(ctx, start_brace, end_brace, table_type, shape);
     let mut shape = shape.reset().increment_additional_indent(); // Will take new line, and additional indentation
 
+    // A table with a single field may be configured to not have a trailing separator, unless the separator has
+    // comments attached to it which must be kept
+    let omit_trailing_sep = ctx.config().omit_single_field_trailing_sep
+        && fields.len() == 1
+        && !fields.pairs().any(|pair| {
+            matches!(
+                pair.punctuation(),
+                Some(punctuation) if trivia_util::token_contains_comments(punctuation)
+            )
+        });
+
     let current_fields = fields.pairs();
     let mut fields = Punctuated::new();
-
-    let symbol = &table_sep_character(ctx.config().table_sep);
+    let mut braces = braces;
 
     for pair in current_fields {
         let (field, punctuation) = (pair.value(), pair.punctuation());
@@ -380,6 +436,22 @@ where
         // Add newline trivia to the end of the symbol
         trailing_trivia.push(create_newline_trivia(ctx));
 
+        // When only formatting whitespace, a final field without a separator does not gain one.
+        // Instead, the trailing trivia is placed before the closing brace
+        if (ctx.config().whitespace_only && punctuation.is_none()) || omit_trailing_sep {
+            let (start_brace, end_brace) = braces.tokens();
+            let end_brace = end_brace.update_leading_trivia(FormatTriviaType::Replace(
+                trailing_trivia
+                    .into_iter()
+                    .chain(end_brace.leading_trivia().cloned())
+                    .collect(),
+            ));
+            braces = ContainedSpan::new(start_brace.to_owned(), end_brace);
+            fields.push(Pair::new(formatted_field, None));
+            continue;
+        }
+
+        let symbol = &table_separator(ctx, punctuation);
         let symbol = match punctuation {
             Some(punctuation) => fmt_symbol!(ctx, punctuation, symbol, shape),
             None => TokenReference::symbol(symbol).unwrap(),
@@ -437,6 +509,248 @@ fn should_expand(table_constructor: &TableConstructor) -> bool {
     }
 }
 
+/// Whether the key of an `[key] = value` field is a simple literal, i.e. a number or a string
+fn is_simple_literal_key(key: &Expression) -> bool {
+    match key {
+        Expression::Value { value, .. } => matches!(**value, Value::Number(_) | Value::String(_)),
+        _ => false,
+    }
+}
+
+/// Whether any of the trivia leading a field is a comment or a blank line, which separates it from the group of
+/// aligned fields before it
+fn starts_alignment_group<'a>(mut leading_trivia: impl Iterator<Item = &'a Token>) -> bool {
+    leading_trivia.any(|trivia| {
+        trivia_util::trivia_is_comment(trivia) || trivia_util::trivia_is_newline(trivia)
+    })
+}
+
+/// Pads the equals sign of consecutive fields in a multiline table, so that their values line up.
+/// `alignable_key` returns the width of the key of a field which can be aligned, and whether it starts a new group,
+/// or `None` if the field cannot be aligned.
+///
+/// Fields are aligned in groups, which are separated by a blank line, a comment, or a field which cannot be aligned.
+/// A group is left unaligned if aligning it would take a field over the column width.
+fn align_keys(
+    fields: Punctuated<Field>,
+    shape: Shape,
+    alignable_key: impl Fn(&Field) -> Option<(usize, bool)>,
+) -> Punctuated<Field> {
+    let field_shape = shape.reset().increment_additional_indent();
+
+    // The key width of each field which can be aligned, and whether it starts a new group
+    let alignable_fields: Vec<Option<(usize, bool)>> = fields.iter().map(alignable_key).collect();
+
+    let mut groups: Vec<Vec<usize>> = vec![Vec::new()];
+    for (index, alignable_field) in alignable_fields.iter().enumerate() {
+        match alignable_field {
+            Some((_, starts_group)) => {
+                if *starts_group {
+                    groups.push(Vec::new());
+                }
+                groups.last_mut().unwrap().push(index);
+            }
+            None => groups.push(Vec::new()),
+        }
+    }
+
+    let key_width = |index: usize| alignable_fields[index].expect("field is not alignable").0;
+    let value_widths: Vec<usize> = fields
+        .iter()
+        .map(|field| match field {
+            Field::NameKey { value, .. } | Field::ExpressionKey { value, .. } => {
+                strip_trivia(value).to_string().len()
+            }
+            _ => 0,
+        })
+        .collect();
+
+    // The padding to place before the equals sign of each aligned field
+    let mut paddings = vec![None; alignable_fields.len()];
+    for group in groups.iter().filter(|group| group.len() > 1) {
+        let max_key_width = group.iter().map(|&index| key_width(index)).max().unwrap();
+        let fits = group.iter().all(|&index| {
+            // 4 = " = " and ","
+            !field_shape
+                .add_width(max_key_width + 4 + value_widths[index])
+                .over_budget()
+        });
+
+        if fits {
+            for &index in group {
+                paddings[index] = Some(1 + max_key_width - key_width(index));
+            }
+        }
+    }
+
+    let pad = |equal: TokenReference, padding: usize| {
+        equal.update_leading_trivia(FormatTriviaType::Replace(vec![Token::new(
+            TokenType::spaces(padding),
+        )]))
+    };
+
+    fields
+        .into_pairs()
+        .zip(paddings)
+        .map(|(pair, padding)| {
+            pair.map(|field| match (field, padding) {
+                (Field::NameKey { key, equal, value }, Some(padding)) => Field::NameKey {
+                    key,
+                    equal: pad(equal, padding),
+                    value,
+                },
+                (
+                    Field::ExpressionKey {
+                        brackets,
+                        key,
+                        equal,
+                        value,
+                    },
+                    Some(padding),
+                ) => Field::ExpressionKey {
+                    brackets,
+                    key,
+                    equal: pad(equal, padding),
+                    value,
+                },
+                (field, _) => field,
+            })
+        })
+        .collect()
+}
+
+/// Pads the equals sign of each `[key] = value` field in a multiline table, so that the values of consecutive fields
+/// line up:
+/// ```lua
+/// local foo = {
+///     [1]     = "one",
+///     ["two"] = 2,
+/// }
+/// ```
+/// Only fields with simple literal keys, which do not span multiple lines, are aligned. They are grouped in the same way
+/// as [`align_name_keys`].
+fn align_expression_keys(fields: Punctuated<Field>, shape: Shape) -> Punctuated<Field> {
+    align_keys(fields, shape, |field| match field {
+        Field::ExpressionKey {
+            brackets,
+            key,
+            value,
+            ..
+        } if is_simple_literal_key(key) => {
+            let (start_bracket, end_bracket) = brackets.tokens();
+            let key = format!(
+                "{}{}{}",
+                start_bracket.token(),
+                strip_trivia(key),
+                end_bracket.token()
+            );
+            if key.contains('\n') || value.to_string().contains('\n') {
+                return None;
+            }
+
+            Some((
+                key.chars().count(),
+                starts_alignment_group(start_bracket.leading_trivia()),
+            ))
+        }
+        _ => None,
+    })
+}
+
+/// Pads the equals sign of each `key = value` field in a multiline table, so that the values of consecutive fields line
+/// up:
+/// ```lua
+/// local foo = {
+///     width  = 10,
+///     height = 200,
+/// }
+/// ```
+/// Fields are aligned in groups, which are separated by a blank line, a comment, a field of another kind, or a field
+/// whose value spans multiple lines. A group is left unaligned if aligning it would take a field over the column width.
+fn align_name_keys(fields: Punctuated<Field>, shape: Shape) -> Punctuated<Field> {
+    align_keys(fields, shape, |field| match field {
+        Field::NameKey { key, value, .. } if !value.to_string().contains('\n') => Some((
+            strip_trivia(key).to_string().len(),
+            starts_alignment_group(key.leading_trivia()),
+        )),
+        _ => None,
+    })
+}
+
+fn is_semicolon(token: &TokenReference) -> bool {
+    matches!(
+        token.token_type(),
+        TokenType::Symbol {
+            symbol: Symbol::Semicolon
+        }
+    )
+}
+
+/// Restores the separator between the positional (array) fields and the keyed (hash) fields of a table
+/// back to a semicolon, if a semicolon was originally used there, i.e. `{ 1, 2, 3; foo = "bar" }`.
+/// Only the first such boundary is preserved, all other separators are left as formatted.
+fn preserve_boundary_semicolon(
+    original_fields: &Punctuated<Field>,
+    formatted_fields: Punctuated<Field>,
+) -> Punctuated<Field> {
+    let boundary = original_fields
+        .pairs()
+        .zip(original_fields.iter().skip(1))
+        .position(|(pair, next_field)| {
+            matches!(pair.value(), Field::NoKey(_))
+                && !matches!(next_field, Field::NoKey(_))
+                && matches!(pair.punctuation(), Some(punctuation) if is_semicolon(punctuation))
+        });
+
+    match boundary {
+        Some(boundary) => formatted_fields
+            .into_pairs()
+            .enumerate()
+            .map(|(idx, pair)| match pair {
+                Pair::Punctuated(field, punctuation) if idx == boundary => {
+                    let semicolon = TokenReference::new(
+                        punctuation.leading_trivia().cloned().collect(),
+                        Token::new(TokenType::Symbol {
+                            symbol: Symbol::Semicolon,
+                        }),
+                        punctuation.trailing_trivia().cloned().collect(),
+                    );
+                    Pair::Punctuated(field, semicolon)
+                }
+                pair => pair,
+            })
+            .collect(),
+        None => formatted_fields,
+    }
+}
+
+/// Formats the fields of a table constructor onto multiple lines
+fn format_multiline_table_constructor(
+    ctx: &Context,
+    table_constructor: &TableConstructor,
+    shape: Shape,
+) -> (ContainedSpan, Punctuated<Field>) {
+    let (braces, fields) = format_multiline_table(
+        ctx,
+        table_constructor.braces(),
+        table_constructor.fields(),
+        format_field,
+        shape,
+    );
+
+    let fields = if ctx.config().align_table_expression_keys {
+        align_expression_keys(fields, shape)
+    } else {
+        fields
+    };
+
+    if ctx.config().align_table_name_keys {
+        (braces, align_name_keys(fields, shape))
+    } else {
+        (braces, fields)
+    }
+}
+
 pub fn format_table_constructor(
     ctx: &Context,
     table_constructor: &TableConstructor,
@@ -444,8 +758,44 @@ pub fn format_table_constructor(
 ) -> TableConstructor {
     let (start_brace, end_brace) = table_constructor.braces().tokens();
 
+    // Only memoize tables taken from the input: tables created during formatting have no positions to tell them apart
+    if start_brace.token().start_position().line() == 0 {
+        return format_table_constructor_uncached(ctx, table_constructor, shape);
+    }
+
+    let mut hasher = DefaultHasher::new();
+    table_constructor.to_string().hash(&mut hasher);
+    let key = TableCacheKey {
+        braces_range: (
+            start_brace.token().start_position().bytes(),
+            end_brace.token().end_position().bytes(),
+        ),
+        source_hash: hasher.finish(),
+        shape,
+        context_state: ctx.state(),
+    };
+
+    if let Some(cached) = TABLE_CONSTRUCTOR_CACHE.with(|cache| cache.borrow().get(&key).cloned()) {
+        return cached;
+    }
+
+    let formatted = format_table_constructor_uncached(ctx, table_constructor, shape);
+    TABLE_CONSTRUCTOR_CACHE.with(|cache| cache.borrow_mut().insert(key, formatted.clone()));
+    formatted
+}
+
+fn format_table_constructor_uncached(
+    ctx: &Context,
+    table_constructor: &TableConstructor,
+    shape: Shape,
+) -> TableConstructor {
+    let (start_brace, end_brace) = table_constructor.braces().tokens();
+
     // Determine if we need to force the table multiline
-    let should_expand = should_expand(table_constructor);
+    let layout_directive = ctx.layout_directive();
+    let should_expand = should_expand(table_constructor)
+        || (layout_directive == Some(LayoutDirective::Expand)
+            && !table_constructor.fields().is_empty());
 
     let table_type = match (should_expand, table_constructor.fields().iter().next()) {
         // We should expand, so force multiline
@@ -475,10 +825,11 @@ pub fn format_table_constructor(
                 true => TableType::MultiLine,
                 false => {
                     // Determine if there was a new line at the end of the start brace
-                    // If so, then we should always be multiline
-                    if start_brace
-                        .trailing_trivia()
-                        .any(trivia_util::trivia_is_newline)
+                    // If so, then we should always be multiline, unless a collapse directive is present
+                    if layout_directive != Some(LayoutDirective::Collapse)
+                        && start_brace
+                            .trailing_trivia()
+                            .any(trivia_util::trivia_is_newline)
                     {
                         TableType::MultiLine
                     } else {
@@ -496,20 +847,36 @@ pub fn format_table_constructor(
             let braces = create_table_braces(ctx, start_brace, end_brace, table_type, shape);
             (braces, Punctuated::new())
         }
-        TableType::SingleLine => format_singleline_table(
-            ctx,
-            table_constructor.braces(),
-            table_constructor.fields(),
-            format_field,
-            shape,
-        ),
-        TableType::MultiLine => format_multiline_table(
-            ctx,
-            table_constructor.braces(),
-            table_constructor.fields(),
-            format_field,
-            shape,
-        ),
+        TableType::SingleLine => {
+            let (braces, fields) = format_singleline_table(
+                ctx,
+                table_constructor.braces(),
+                table_constructor.fields(),
+                format_field,
+                shape,
+            );
+
+            // If the fields are wider than the maximum inline width, or the table is nested too deeply, then the table
+            // should be expanded instead, unless a collapse directive is present
+            let fields_string = fields.to_string();
+            if layout_directive != Some(LayoutDirective::Collapse)
+                && !fields_string.contains('\n')
+                && (fields_string.len() > ctx.config().max_inline_width
+                    || trivia_util::table_nesting_depth(table_constructor)
+                        > ctx.config().max_inline_nesting_depth)
+            {
+                format_multiline_table_constructor(ctx, table_constructor, shape)
+            } else {
+                (braces, fields)
+            }
+        }
+        TableType::MultiLine => format_multiline_table_constructor(ctx, table_constructor, shape),
+    };
+
+    let fields = if ctx.config().preserve_table_boundary_semicolon {
+        preserve_boundary_semicolon(table_constructor.fields(), fields)
+    } else {
+        fields
     };
 
     TableConstructor::new()
@@ -31,6 +31,7 @@ where
         .update_trailing_trivia(FormatTriviaType::Replace(vec![]))
 }
 
+/// Strips all leading trivia from a specific node
 pub fn strip_leading_trivia<T>(item: &T) -> T
 where
     T: UpdateLeadingTrivia,
@@ -38,6 +39,7 @@ where
     item.update_leading_trivia(FormatTriviaType::Replace(vec![]))
 }
 
+/// Strips all trailing trivia from a specific node
 pub fn strip_trailing_trivia<T>(item: &T) -> T
 where
     T: UpdateTrailingTrivia,
@@ -45,14 +47,17 @@ where
     item.update_trailing_trivia(FormatTriviaType::Replace(vec![]))
 }
 
+/// Allows replacing or appending to the leading trivia of a node
 pub trait UpdateLeadingTrivia {
     fn update_leading_trivia(&self, leading_trivia: FormatTriviaType) -> Self;
 }
 
+/// Allows replacing or appending to the trailing trivia of a node
 pub trait UpdateTrailingTrivia {
     fn update_trailing_trivia(&self, trailing_trivia: FormatTriviaType) -> Self;
 }
 
+/// Allows updating both the leading and trailing trivia of a node at once
 pub trait UpdateTrivia {
     fn update_trivia(
         &self,
@@ -387,6 +392,18 @@ define_update_leading_trivia!(Prefix, |this, leading| {
     }
 });
 
+define_update_trailing_trivia!(Prefix, |this, trailing| {
+    match this {
+        Prefix::Name(token_reference) => {
+            Prefix::Name(token_reference.update_trailing_trivia(trailing))
+        }
+        Prefix::Expression(expression) => {
+            Prefix::Expression(expression.update_trailing_trivia(trailing))
+        }
+        other => panic!("unknown node {:?}", other),
+    }
+});
+
 impl<T> UpdateLeadingTrivia for Punctuated<T>
 where
     T: UpdateLeadingTrivia + Clone,
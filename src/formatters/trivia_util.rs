@@ -11,12 +11,16 @@ use full_moon::{
     },
     node::Node,
     tokenizer::{Token, TokenKind, TokenReference, TokenType},
+    visitors::{Visit, Visitor},
 };
+use std::{cell::RefCell, collections::HashMap};
 
+/// Whether the trivia is whitespace
 pub fn trivia_is_whitespace(trivia: &Token) -> bool {
     matches!(trivia.token_kind(), TokenKind::Whitespace)
 }
 
+/// Whether the trivia is a single-line or multi-line comment
 pub fn trivia_is_comment(trivia: &Token) -> bool {
     matches!(
         trivia.token_kind(),
@@ -24,6 +28,7 @@ pub fn trivia_is_comment(trivia: &Token) -> bool {
     )
 }
 
+/// Whether the trivia is whitespace containing a newline
 pub fn trivia_is_newline(trivia: &Token) -> bool {
     if let TokenType::Whitespace { characters } = trivia.token_type() {
         if characters.find('\n').is_some() {
@@ -100,7 +105,7 @@ fn function_args_trailing_trivia(function_args: &FunctionArgs) -> Vec<Token> {
     }
 }
 
-fn suffix_trailing_trivia(suffix: &Suffix) -> Vec<Token> {
+pub fn suffix_trailing_trivia(suffix: &Suffix) -> Vec<Token> {
     match suffix {
         Suffix::Index(index) => match index {
             Index::Brackets { brackets, .. } => {
@@ -119,6 +124,68 @@ fn suffix_trailing_trivia(suffix: &Suffix) -> Vec<Token> {
     }
 }
 
+/// Takes any comments found between the previous segment of a chain and the name of a `.name` or `:name` suffix,
+/// i.e. around the dot or colon, or before the name. Returns the suffix without these comments and the comments
+/// taken, so that they can be moved to the end of the previous segment instead.
+pub fn take_suffix_leading_comments(suffix: &Suffix) -> (Suffix, Vec<Token>) {
+    fn take_comments<'a>(
+        trivia: impl Iterator<Item = &'a Token>,
+        comments: &mut Vec<Token>,
+    ) -> Vec<Token> {
+        let trivia: Vec<Token> = trivia.cloned().collect();
+        if trivia.iter().any(trivia_is_comment) {
+            comments.extend(trivia.into_iter().filter(trivia_is_comment));
+            Vec::new()
+        } else {
+            trivia
+        }
+    }
+
+    let mut comments = Vec::new();
+    let suffix = match suffix {
+        Suffix::Index(Index::Dot { dot, name }) => Suffix::Index(Index::Dot {
+            dot: TokenReference::new(
+                take_comments(dot.leading_trivia(), &mut comments),
+                dot.token().to_owned(),
+                take_comments(dot.trailing_trivia(), &mut comments),
+            ),
+            name: TokenReference::new(
+                take_comments(name.leading_trivia(), &mut comments),
+                name.token().to_owned(),
+                name.trailing_trivia().cloned().collect(),
+            ),
+        }),
+        Suffix::Call(Call::MethodCall(method_call)) => {
+            let colon = method_call.colon_token();
+            let name = method_call.name();
+            Suffix::Call(Call::MethodCall(
+                method_call
+                    .to_owned()
+                    .with_colon_token(TokenReference::new(
+                        take_comments(colon.leading_trivia(), &mut comments),
+                        colon.token().to_owned(),
+                        take_comments(colon.trailing_trivia(), &mut comments),
+                    ))
+                    .with_name(TokenReference::new(
+                        take_comments(name.leading_trivia(), &mut comments),
+                        name.token().to_owned(),
+                        name.trailing_trivia().cloned().collect(),
+                    )),
+            ))
+        }
+        _ => suffix.to_owned(),
+    };
+
+    (suffix, comments)
+}
+
+/// Whether any of the trivia is a single line comment, meaning that anything following it must be placed on a new line
+pub fn trivia_contains_single_line_comment(trivia: &[Token]) -> bool {
+    trivia
+        .iter()
+        .any(|trivia| trivia.token_kind() == TokenKind::SingleLineComment)
+}
+
 #[cfg(feature = "luau")]
 fn indexed_type_info_trailing_trivia(indexed_type_info: &IndexedTypeInfo) -> Vec<Token> {
     match indexed_type_info {
@@ -902,19 +969,23 @@ pub fn token_trivia_contains_comments<'a>(trivia: impl Iterator<Item = &'a Token
     false
 }
 
+/// Whether the token has any comments in its leading trivia
 pub fn token_contains_leading_comments(token_ref: &TokenReference) -> bool {
     token_trivia_contains_comments(token_ref.leading_trivia())
 }
 
+/// Whether the token has any comments in its trailing trivia
 pub fn token_contains_trailing_comments(token_ref: &TokenReference) -> bool {
     token_trivia_contains_comments(token_ref.trailing_trivia())
 }
 
+/// Whether the token has any comments in its leading or trailing trivia
 pub fn token_contains_comments(token_ref: &TokenReference) -> bool {
     token_trivia_contains_comments(token_ref.leading_trivia())
         || token_trivia_contains_comments(token_ref.trailing_trivia())
 }
 
+/// Whether any token within the node has comments attached to it
 pub fn contains_comments(node: impl Node) -> bool {
     node.tokens().into_iter().any(token_contains_comments)
 }
@@ -972,3 +1043,129 @@ pub fn expression_contains_inline_comments(expression: &Expression) -> bool {
         _ => false,
     }
 }
+
+thread_local! {
+    /// The nesting depth of every table constructor and parenthesised function call arguments in the input of the
+    /// current formatting run, keyed by the byte positions of their opening and closing tokens
+    static NESTING_DEPTHS: RefCell<HashMap<(usize, usize), usize>> = RefCell::new(HashMap::new());
+}
+
+/// Returns the key used to look up the nesting depth of a node delimited by the given tokens, if they are taken from
+/// the input. Tokens created during formatting have no positions to tell them apart.
+fn nesting_depth_key(start: &TokenReference, end: &TokenReference) -> Option<(usize, usize)> {
+    match start.token().start_position().line() {
+        0 => None,
+        _ => Some((
+            start.token().start_position().bytes(),
+            end.token().end_position().bytes(),
+        )),
+    }
+}
+
+/// Calculates how deeply table constructors and parenthesised function call arguments are nested whilst visiting a
+/// node. Each depth is calculated bottom-up from the depths of the nodes within it, so a node is visited only once.
+struct NestingDepth {
+    /// The maximum depth found so far within each node currently being visited, with the visited node itself last
+    depths: Vec<usize>,
+    /// The nesting depth of each node visited which was taken from the input
+    recorded: HashMap<(usize, usize), usize>,
+}
+
+impl Default for NestingDepth {
+    fn default() -> Self {
+        Self {
+            depths: vec![0],
+            recorded: HashMap::new(),
+        }
+    }
+}
+
+impl NestingDepth {
+    fn enter(&mut self) {
+        self.depths.push(0);
+    }
+
+    fn exit(&mut self, key: Option<(usize, usize)>) {
+        let depth = self.depths.pop().unwrap() + 1;
+        if let Some(key) = key {
+            self.recorded.insert(key, depth);
+        }
+
+        let parent = self.depths.last_mut().unwrap();
+        *parent = (*parent).max(depth);
+    }
+
+    fn max_depth(&self) -> usize {
+        self.depths[0]
+    }
+}
+
+impl Visitor for NestingDepth {
+    fn visit_table_constructor(&mut self, _: &TableConstructor) {
+        self.enter();
+    }
+
+    fn visit_table_constructor_end(&mut self, table_constructor: &TableConstructor) {
+        let (start_brace, end_brace) = table_constructor.braces().tokens();
+        self.exit(nesting_depth_key(start_brace, end_brace));
+    }
+
+    fn visit_function_args(&mut self, function_args: &FunctionArgs) {
+        if let FunctionArgs::Parentheses { .. } = function_args {
+            self.enter();
+        }
+    }
+
+    fn visit_function_args_end(&mut self, function_args: &FunctionArgs) {
+        if let FunctionArgs::Parentheses { parentheses, .. } = function_args {
+            let (start_parens, end_parens) = parentheses.tokens();
+            self.exit(nesting_depth_key(start_parens, end_parens));
+        }
+    }
+}
+
+/// Returns how deeply table constructors and parenthesised function call arguments are nested within a node, including
+/// the node itself. For example, the table `{ a = { b = f(c) } }` has a nesting depth of 3.
+pub fn nesting_depth(node: &impl Visit) -> usize {
+    let mut nesting_depth = NestingDepth::default();
+    node.visit(&mut nesting_depth);
+    nesting_depth.max_depth()
+}
+
+/// Calculates the nesting depth of every table constructor and parenthesised function call arguments within the
+/// input block in a single pass, so that looking them up whilst formatting does not revisit the nodes within them
+pub fn compute_nesting_depths(block: &Block) {
+    let mut nesting_depth = NestingDepth::default();
+    block.visit(&mut nesting_depth);
+    NESTING_DEPTHS.with(|depths| *depths.borrow_mut() = nesting_depth.recorded);
+}
+
+/// Clears the nesting depths calculated for a previous formatting run
+pub fn clear_nesting_depths() {
+    NESTING_DEPTHS.with(|depths| depths.borrow_mut().clear());
+}
+
+/// Looks up the nesting depth calculated for a node delimited by the given tokens, if it was taken from the input
+fn lookup_nesting_depth(start: &TokenReference, end: &TokenReference) -> Option<usize> {
+    let key = nesting_depth_key(start, end)?;
+    NESTING_DEPTHS.with(|depths| depths.borrow().get(&key).copied())
+}
+
+/// Returns the [`nesting_depth`] of a table constructor, using the depth calculated for the input where possible
+pub fn table_nesting_depth(table_constructor: &TableConstructor) -> usize {
+    let (start_brace, end_brace) = table_constructor.braces().tokens();
+    lookup_nesting_depth(start_brace, end_brace).unwrap_or_else(|| nesting_depth(table_constructor))
+}
+
+/// Returns the [`nesting_depth`] of function call arguments, using the depth calculated for the input where possible
+pub fn function_args_nesting_depth(function_args: &FunctionArgs) -> usize {
+    match function_args {
+        FunctionArgs::Parentheses { parentheses, .. } => {
+            let (start_parens, end_parens) = parentheses.tokens();
+            lookup_nesting_depth(start_parens, end_parens)
+                .unwrap_or_else(|| nesting_depth(function_args))
+        }
+        FunctionArgs::TableConstructor(table_constructor) => table_nesting_depth(table_constructor),
+        _ => nesting_depth(function_args),
+    }
+}
@@ -1,13 +1,32 @@
+use full_moon::{
+    ast::{Ast, Stmt},
+    node::Node,
+    tokenizer::{Token, TokenReference},
+};
 use serde::Deserialize;
+use std::{
+    convert::TryFrom,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
 #[macro_use]
 mod context;
 mod formatters;
 mod shape;
+mod sourcemap;
+pub mod trivia;
 mod verify_ast;
 
+pub use sourcemap::SourceMapping;
+pub use verify_ast::{AstVerifier, Mismatch};
+
 /// The type of indents to use when indenting
-#[derive(Debug, Copy, Clone, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
 pub enum IndentType {
     /// Indent using tabs (`\t`)
     Tabs,
@@ -17,7 +36,103 @@ pub enum IndentType {
 
 impl Default for IndentType {
     fn default() -> Self {
-        IndentType::Tabs
+        Config::DEFAULT_INDENT_TYPE
+    }
+}
+
+/// The dialects of Lua which StyLua can parse and format, determined by the features it was compiled with.
+/// Lua 5.1 is always supported, as the other dialects are supersets of it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SupportedDialects {
+    /// Whether Lua 5.2 syntax, such as `goto` and labels, is supported. Enabled by the `lua52` feature.
+    pub lua52: bool,
+    /// Whether Luau syntax, such as type annotations and compound assignments, is supported. Enabled by the `luau` feature.
+    pub luau: bool,
+}
+
+impl SupportedDialects {
+    /// Returns the names of the supported dialects, starting with Lua 5.1
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut names = vec!["Lua 5.1"];
+        if self.lua52 {
+            names.push("Lua 5.2");
+        }
+        if self.luau {
+            names.push("Luau");
+        }
+        names
+    }
+}
+
+impl std::fmt::Display for SupportedDialects {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "{}", self.names().join(", "))
+    }
+}
+
+/// Returns the dialects of Lua supported by this build of StyLua, for example to report its capabilities
+pub fn supported_dialects() -> SupportedDialects {
+    SupportedDialects {
+        lua52: cfg!(feature = "lua52"),
+        luau: cfg!(feature = "luau"),
+    }
+}
+
+/// Returns the effective configuration used when formatting with the provided config, for example so that editors can
+/// display the settings which apply to a file.
+///
+/// The provided config is already the result of merging a `stylua.toml` over the defaults. When [`Config::whitespace_only`]
+/// is enabled, any options which would add, remove or change tokens are disabled. Comment directives such as
+/// `-- stylua: ignore` or `-- stylua: expand` only change how individual statements are laid out, and do not override
+/// any config options. This is the config used by [`format_code`], and every other entry point.
+pub fn resolve_config(config: Config) -> Config {
+    effective_config(config)
+}
+
+/// Applies the overrides of any options which depend on other options. The formatting [`context::Context`] uses this
+/// directly, so it is the single source of truth for the config used when formatting.
+pub(crate) fn effective_config(config: Config) -> Config {
+    // When only formatting whitespace, disable any options which would add, remove or change tokens
+    match config.whitespace_only {
+        true => Config {
+            no_call_parentheses: false,
+            extra_sep_at_table_end: false,
+            merge_string_concatenation: false,
+            reindent_multiline_strings: false,
+            parenthesise_mixed_and_or: false,
+            omit_single_field_trailing_sep: false,
+            ..config
+        },
+        false => config,
+    }
+}
+
+/// Detects the type of indentation used by the given code, for example to report how much of a codebase would change
+/// when formatted with a different [`indent_type`]. This does not affect formatting.
+///
+/// The heuristic is simple: every line which starts with whitespace is sampled, and counted as tab indented if its
+/// first character is a tab, or space indented if it is a space. The more common type is returned. Lines within
+/// multiline strings and comments are sampled too. Returns `None` if no lines are indented, or if both are equally common.
+pub fn detect_indent_type(code: &str) -> Option<IndentType> {
+    let (mut tabs, mut spaces) = (0usize, 0usize);
+
+    for line in code.lines() {
+        // Skip lines which are only whitespace, as they are not indenting anything
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match line.chars().next() {
+            Some('\t') => tabs += 1,
+            Some(' ') => spaces += 1,
+            _ => (),
+        }
+    }
+
+    match tabs.cmp(&spaces) {
+        std::cmp::Ordering::Greater => Some(IndentType::Tabs),
+        std::cmp::Ordering::Less => Some(IndentType::Spaces),
+        std::cmp::Ordering::Equal => None,
     }
 }
 
@@ -67,14 +182,97 @@ pub enum QuoteStyle {
 
 impl Default for QuoteStyle {
     fn default() -> Self {
-        QuoteStyle::AutoPreferDouble
+        Config::DEFAULT_QUOTE_STYLE
+    }
+}
+
+/// A list of prefixes for comments which should be kept verbatim, such as `--!` for Luau directives or `---@` for
+/// annotations. A comment matches if its full text, including the leading `--` or `--[[`, starts with a prefix.
+///
+/// The list has a fixed capacity, so that [`Config`] can remain `Copy`: it can hold up to
+/// [`VerbatimCommentPrefixes::MAX_PREFIXES`] prefixes, each at most [`VerbatimCommentPrefixes::MAX_PREFIX_LEN`] bytes long.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "Vec<String>")]
+pub struct VerbatimCommentPrefixes {
+    prefixes:
+        [[u8; VerbatimCommentPrefixes::MAX_PREFIX_LEN]; VerbatimCommentPrefixes::MAX_PREFIXES],
+    lengths: [usize; VerbatimCommentPrefixes::MAX_PREFIXES],
+    count: usize,
+}
+
+impl VerbatimCommentPrefixes {
+    /// The maximum number of prefixes which can be configured
+    pub const MAX_PREFIXES: usize = 8;
+    /// The maximum length of each prefix, in bytes
+    pub const MAX_PREFIX_LEN: usize = 16;
+
+    /// Returns an iterator over the configured prefixes
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.prefixes[..self.count]
+            .iter()
+            .zip(&self.lengths)
+            .map(|(prefix, length)| {
+                std::str::from_utf8(&prefix[..*length]).expect("prefixes are created from strings")
+            })
+    }
+
+    /// Returns the configured prefixes
+    pub fn to_vec(&self) -> Vec<String> {
+        self.iter().map(str::to_owned).collect()
+    }
+
+    /// Whether the given comment, including its leading `--` or `--[[`, starts with any of the prefixes
+    pub fn matches(&self, comment: &str) -> bool {
+        self.iter().any(|prefix| comment.starts_with(prefix))
+    }
+}
+
+impl TryFrom<Vec<String>> for VerbatimCommentPrefixes {
+    type Error = String;
+
+    /// Creates the list from the given prefixes. Returns an error if there are more than
+    /// [`VerbatimCommentPrefixes::MAX_PREFIXES`] prefixes, or one is longer than [`VerbatimCommentPrefixes::MAX_PREFIX_LEN`] bytes.
+    fn try_from(prefixes: Vec<String>) -> Result<Self, Self::Error> {
+        if prefixes.len() > Self::MAX_PREFIXES {
+            return Err(format!(
+                "at most {} verbatim comment prefixes can be configured, but {} were given",
+                Self::MAX_PREFIXES,
+                prefixes.len()
+            ));
+        }
+
+        let mut verbatim_comment_prefixes = Self::default();
+        for (index, prefix) in prefixes.iter().enumerate() {
+            if prefix.len() > Self::MAX_PREFIX_LEN {
+                return Err(format!(
+                    "verbatim comment prefix `{}` is longer than the maximum of {} bytes",
+                    prefix,
+                    Self::MAX_PREFIX_LEN
+                ));
+            }
+
+            verbatim_comment_prefixes.prefixes[index][..prefix.len()]
+                .copy_from_slice(prefix.as_bytes());
+            verbatim_comment_prefixes.lengths[index] = prefix.len();
+        }
+        verbatim_comment_prefixes.count = prefixes.len();
+
+        Ok(verbatim_comment_prefixes)
+    }
+}
+
+impl std::fmt::Debug for VerbatimCommentPrefixes {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.debug_list().entries(self.iter()).finish()
     }
 }
 
 /// An optional formatting range.
 /// If provided, only content within these boundaries (inclusive) will be formatted.
 /// Both boundaries are optional, and are given as byte offsets from the beginning of the file.
-#[derive(Debug, Copy, Clone, Deserialize)]
+/// If the file starts with a shebang line (e.g. `#!/usr/bin/env lua`), or lines kept verbatim by
+/// [`Config::preserve_first_lines`], the offsets still include those lines.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize)]
 pub struct Range {
     start: Option<usize>,
     end: Option<usize>,
@@ -86,6 +284,14 @@ impl Range {
     pub fn from_values(start: Option<usize>, end: Option<usize>) -> Self {
         Self { start, end }
     }
+
+    /// Moves both boundaries back by the given number of bytes, saturating at the beginning of the file
+    fn shift_back(self, bytes: usize) -> Self {
+        Self {
+            start: self.start.map(|start| start.saturating_sub(bytes)),
+            end: self.end.map(|end| end.saturating_sub(bytes)),
+        }
+    }
 }
 
 /// The configuration to use when formatting.
@@ -121,14 +327,361 @@ pub struct Config {
     /// Whether to add an additional space inside of an empty table.
     /// Default is recommended for opinionated reasons.
     extra_space_in_empty_table: bool,
+    /// Whether to keep a semicolon separating the positional fields of a table from its keyed fields
+    /// (i.e. `{ 1, 2; foo = "bar" }`), if one was already present. All other separators still follow `table_sep`.
+    preserve_table_boundary_semicolon: bool,
+    /// Whether to indent `else` and `elseif` clauses one level deeper than their `if`, rather than aligning them with it.
+    /// The `end` of the if statement is always aligned with the `if`.
+    indent_else_clauses: bool,
+    /// Whether to align the values of consecutive `[key] = value` fields in multiline tables, by padding before the equals
+    /// sign. Only fields whose keys are simple literals, and which do not span multiple lines, are aligned. They are
+    /// aligned in groups in the same way as [`align_table_name_keys`], and a group is not aligned if this would take a
+    /// field over the column width.
+    align_table_expression_keys: bool,
+    /// Whether to keep trivial blocks on a single line, if they fit and contain no comments.
+    /// Currently, this only applies to `do ... end` blocks containing a single statement, such as `do return x end`.
+    /// Blocks declaring a local, such as `do local x = 1 end`, are never collapsed, as they limit its scope.
+    collapse_simple_statement: bool,
+    /// Whether to merge concatenations consisting purely of quoted string literals into a single string literal,
+    /// i.e. `"a" .. "\n" .. "b"` becomes `"a\nb"`. The merged string follows `quote_style`.
+    merge_string_concatenation: bool,
+    /// Whether to pad the inside of parentheses around expressions with spaces, i.e. `( a + b ) * c`.
+    /// This does not apply to the parentheses of function calls, nor to parentheses used as a prefix, such as `(a or b).c`.
+    spaces_inside_parentheses: bool,
+    /// The maximum number of links a call chain (i.e. `foo:bar():baz()`) can contain whilst staying on a single line.
+    /// Every call and index suffix is a link, so `a.b:c()()` has 4 links. Chains with more links are always hung,
+    /// regardless of width, placing each `:method()` call and `.name` index on its own line. Chains within the limit
+    /// are only hung if they do not fit within the column width.
+    max_inline_method_chain: usize,
+    /// Whether number literals should keep their notation as written, i.e. `1.0` is never changed to `1` and
+    /// exponents are left untouched. Only the case of prefixes and exponent markers, and a missing leading `0`, are normalised.
+    /// If disabled, a trailing decimal point is completed (`5.` becomes `5.0`) and a redundant `+` is removed from exponents.
+    preserve_number_notation: bool,
+    /// Whether a space should be placed between the `function` keyword and the parameters of an anonymous function,
+    /// i.e. `function () end` rather than `function() end`.
+    space_after_anonymous_function: bool,
+    /// Whether the contents of multiline long bracket strings (`[[ ... ]]`) should be reindented relative to the
+    /// indentation of the code containing them. This changes the value of the string, so it is disabled by default.
+    reindent_multiline_strings: bool,
+    /// Whether to add parentheses around `and` operations used directly within an `or` operation,
+    /// i.e. `a and b or c` becomes `(a and b) or c`.
+    parenthesise_mixed_and_or: bool,
+    /// Whether the trailing comments of consecutive single line statements should be aligned to a common column.
+    /// Statements separated by a blank line or a comment, or without a trailing comment, are aligned separately.
+    align_trailing_comments: bool,
+    /// Whether formatting should be restricted to only changing whitespace, indentation and line breaks.
+    /// No tokens are added, removed or changed: parentheses, semicolons and separators are kept as written,
+    /// and string, number and comment text is left untouched. This overrides any other option which would change tokens.
+    whitespace_only: bool,
+    /// Whether to pad the inside of the brackets of index expressions with spaces, i.e. `t[ key ]`.
+    /// Index brackets containing a long bracket string, such as `t[ [[key]] ]`, are always padded.
+    spaces_inside_index_brackets: bool,
+    /// The maximum width of the contents of a table constructor, or of the arguments of a function call, which can be kept
+    /// on a single line. Tables and calls with wider contents are expanded onto multiple lines, even if they would fit
+    /// within the column width. The width excludes the braces or parentheses, and any padding inside of them.
+    max_inline_width: usize,
+    /// Whether the braces of a table should hug the parentheses of a function call when it is the only argument and
+    /// is expanded, i.e. `foo({` ... `})`. If disabled, the arguments are expanded instead, placing the table on its own line.
+    /// This has no effect when the parentheses are omitted due to [`no_call_parentheses`].
+    hug_table_argument: bool,
+    /// Whether the `then` or `do` keyword following a condition which has been hung over multiple lines should be
+    /// attached to the end of the last line of the condition, rather than placed on its own line.
+    /// This applies to `if`, `elseif` and `while` statements.
+    attach_hanging_condition_keyword: bool,
+    /// Whether comments trailing the condition of an `if`, `elseif` or `while` statement should be moved after the
+    /// `then` or `do` keyword, rather than forcing the condition onto multiple lines.
+    /// Comments within the condition itself still force it to be formatted across multiple lines.
+    inline_condition_trailing_comments: bool,
+    /// Whether `local f = function(...) ... end` should be rewritten as `local function f(...) ... end`.
+    /// This is only done when it is safe to do so, i.e. when `f` is not referenced within the function body,
+    /// where it would otherwise refer to a different variable.
+    prefer_local_function: bool,
+    /// Whether an anonymous function passed as the last argument of a call should always stay attached to the call,
+    /// i.e. `foo(bar, function()` ... `end)`, rather than the arguments being expanded when the first line is too wide.
+    /// This only applies when the arguments before the function fit on a single line and there are no comments.
+    attach_trailing_callback: bool,
+    /// The maximum size of input, in bytes, which will be formatted. Larger input is rejected with
+    /// [`Error::InputTooLarge`] before it is parsed, to avoid spending excessive time and memory on accidentally huge
+    /// inputs.
+    max_input_size: usize,
+    /// Whether to omit the trailing separator of a table with a single field when it is expanded onto multiple lines,
+    /// e.g. because its value is a multiline function. Tables with more than one field are unaffected.
+    omit_single_field_trailing_sep: bool,
+    /// Whether to keep parentheses written around the condition of an `if`, `elseif`, `while` or `repeat ... until`
+    /// statement, e.g. `if (a and b) then`, rather than removing them. Parentheses around a single value, such as
+    /// `if (a) then`, are still removed, as they are elsewhere.
+    preserve_condition_parentheses: bool,
+    /// Whether the body of a trailing callback kept attached to its call by `attach_trailing_callback` is indented
+    /// an extra level, along with its `end`, to distinguish it from the code surrounding the call.
+    indent_trailing_callback_body: bool,
+    /// The number of lines at the start of the file, such as a generated header, which are passed through verbatim.
+    /// Only the code after these lines is formatted.
+    preserve_first_lines: usize,
+    /// The maximum number of consecutive blank lines to keep within the body of a function. Outside of function bodies,
+    /// a single blank line is kept.
+    max_blank_lines_in_function: usize,
+    /// Whether a `cond and a or b` ternary idiom which does not fit on a single line is hung as a unit, placing both the
+    /// `and` and the `or` onto new lines at the same indent level, rather than hanging the `and` and `or` independently.
+    hang_ternary_idiom: bool,
+    /// Whether to place spaces around the `::` operator of a Luau type assertion, i.e. `x :: T`, rather than `x::T`.
+    spaces_around_type_assertion: bool,
+    /// Whether to ensure a blank line separates the `local x = require(...)` statements at the start of a file from the
+    /// code following them. Blank lines are collapsed beforehand, so exactly one blank line is left between them.
+    blank_line_after_requires: bool,
+    /// Whether to align the values of consecutive `key = value` fields in multiline tables, by padding before the equals
+    /// sign. Fields are aligned in groups separated by blank lines, comments, other kinds of fields and multiline values.
+    /// A group is not aligned if this would take a field over the column width.
+    align_table_name_keys: bool,
+    /// Whether to convert the line endings inside multiline strings, i.e. `[[ ... ]]`, to the configured [`line_endings`].
+    /// This changes the bytes written to the string's contents, so by default they are left untouched.
+    normalize_string_line_endings: bool,
+    /// The maximum depth of nested table constructors and function call arguments which can be kept on a single line,
+    /// counting the table or call itself. Tables and calls nested more deeply are expanded onto multiple lines, even if
+    /// they would fit within the column width. For example, `{ a = { b = 1 } }` has a depth of 2.
+    max_inline_nesting_depth: usize,
+    /// Whether comments on their own lines directly before an `else` or `elseif` keyword should be indented to the same
+    /// level as the keyword, rather than the block preceding it.
+    align_else_leading_comments: bool,
+    /// Prefixes of comments which are kept verbatim, such as `--!` or `---@`. The text of a matching comment is never
+    /// changed, i.e. its trailing whitespace is not trimmed. See [`VerbatimCommentPrefixes`] for its capacity.
+    verbatim_comment_prefixes: VerbatimCommentPrefixes,
 }
 
 impl Config {
+    /// The default value for [`column_width`]
+    pub const DEFAULT_COLUMN_WIDTH: usize = 120;
+    /// The default value for [`indent_type`]
+    pub const DEFAULT_INDENT_TYPE: IndentType = IndentType::Tabs;
+    /// The default value for [`indent_width`]
+    pub const DEFAULT_INDENT_WIDTH: usize = 4;
+    /// The default value for [`quote_style`]
+    pub const DEFAULT_QUOTE_STYLE: QuoteStyle = QuoteStyle::AutoPreferDouble;
+
     /// Creates a new Config with the default values
     pub fn new() -> Self {
         Config::default()
     }
 
+    /// Returns the configured value for [`column_width`]
+    pub fn column_width(&self) -> usize {
+        self.column_width
+    }
+
+    /// Returns the configured value for [`line_endings`]
+    pub fn line_endings(&self) -> LineEndings {
+        self.line_endings
+    }
+
+    /// Returns the configured value for [`indent_type`]
+    pub fn indent_type(&self) -> IndentType {
+        self.indent_type
+    }
+
+    /// Returns the configured value for [`indent_width`]
+    pub fn indent_width(&self) -> usize {
+        self.indent_width
+    }
+
+    /// Returns the configured value for [`quote_style`]
+    pub fn quote_style(&self) -> QuoteStyle {
+        self.quote_style
+    }
+
+    /// Returns the configured value for [`no_call_parentheses`]
+    pub fn no_call_parentheses(&self) -> bool {
+        self.no_call_parentheses
+    }
+
+    /// Returns the configured value for [`table_sep`]
+    pub fn table_sep(&self) -> TableSeparators {
+        self.table_sep
+    }
+
+    /// Returns the configured value for [`extra_sep_at_table_end`]
+    pub fn extra_sep_at_table_end(&self) -> bool {
+        self.extra_sep_at_table_end
+    }
+
+    /// Returns the configured value for [`extra_spaces_inside_table`]
+    pub fn extra_spaces_inside_table(&self) -> bool {
+        self.extra_spaces_inside_table
+    }
+
+    /// Returns the configured value for [`extra_space_in_empty_table`]
+    pub fn extra_space_in_empty_table(&self) -> bool {
+        self.extra_space_in_empty_table
+    }
+
+    /// Returns the configured value for [`preserve_table_boundary_semicolon`]
+    pub fn preserve_table_boundary_semicolon(&self) -> bool {
+        self.preserve_table_boundary_semicolon
+    }
+
+    /// Returns the configured value for [`indent_else_clauses`]
+    pub fn indent_else_clauses(&self) -> bool {
+        self.indent_else_clauses
+    }
+
+    /// Returns the configured value for [`align_table_expression_keys`]
+    pub fn align_table_expression_keys(&self) -> bool {
+        self.align_table_expression_keys
+    }
+
+    /// Returns the configured value for [`collapse_simple_statement`]
+    pub fn collapse_simple_statement(&self) -> bool {
+        self.collapse_simple_statement
+    }
+
+    /// Returns the configured value for [`merge_string_concatenation`]
+    pub fn merge_string_concatenation(&self) -> bool {
+        self.merge_string_concatenation
+    }
+
+    /// Returns the configured value for [`spaces_inside_parentheses`]
+    pub fn spaces_inside_parentheses(&self) -> bool {
+        self.spaces_inside_parentheses
+    }
+
+    /// Returns the configured value for [`max_inline_method_chain`]
+    pub fn max_inline_method_chain(&self) -> usize {
+        self.max_inline_method_chain
+    }
+
+    /// Returns the configured value for [`preserve_number_notation`]
+    pub fn preserve_number_notation(&self) -> bool {
+        self.preserve_number_notation
+    }
+
+    /// Returns the configured value for [`space_after_anonymous_function`]
+    pub fn space_after_anonymous_function(&self) -> bool {
+        self.space_after_anonymous_function
+    }
+
+    /// Returns the configured value for [`reindent_multiline_strings`]
+    pub fn reindent_multiline_strings(&self) -> bool {
+        self.reindent_multiline_strings
+    }
+
+    /// Returns the configured value for [`parenthesise_mixed_and_or`]
+    pub fn parenthesise_mixed_and_or(&self) -> bool {
+        self.parenthesise_mixed_and_or
+    }
+
+    /// Returns the configured value for [`align_trailing_comments`]
+    pub fn align_trailing_comments(&self) -> bool {
+        self.align_trailing_comments
+    }
+
+    /// Returns the configured value for [`whitespace_only`]
+    pub fn whitespace_only(&self) -> bool {
+        self.whitespace_only
+    }
+
+    /// Returns the configured value for [`spaces_inside_index_brackets`]
+    pub fn spaces_inside_index_brackets(&self) -> bool {
+        self.spaces_inside_index_brackets
+    }
+
+    /// Returns the configured value for [`max_inline_width`]
+    pub fn max_inline_width(&self) -> usize {
+        self.max_inline_width
+    }
+
+    /// Returns the configured value for [`hug_table_argument`]
+    pub fn hug_table_argument(&self) -> bool {
+        self.hug_table_argument
+    }
+
+    /// Returns the configured value for [`attach_hanging_condition_keyword`]
+    pub fn attach_hanging_condition_keyword(&self) -> bool {
+        self.attach_hanging_condition_keyword
+    }
+
+    /// Returns the configured value for [`inline_condition_trailing_comments`]
+    pub fn inline_condition_trailing_comments(&self) -> bool {
+        self.inline_condition_trailing_comments
+    }
+
+    /// Returns the configured value for [`prefer_local_function`]
+    pub fn prefer_local_function(&self) -> bool {
+        self.prefer_local_function
+    }
+
+    /// Returns the configured value for [`attach_trailing_callback`]
+    pub fn attach_trailing_callback(&self) -> bool {
+        self.attach_trailing_callback
+    }
+
+    /// Returns the configured value for [`max_input_size`]
+    pub fn max_input_size(&self) -> usize {
+        self.max_input_size
+    }
+
+    /// Returns the configured value for [`omit_single_field_trailing_sep`]
+    pub fn omit_single_field_trailing_sep(&self) -> bool {
+        self.omit_single_field_trailing_sep
+    }
+
+    /// Returns the configured value for [`preserve_condition_parentheses`]
+    pub fn preserve_condition_parentheses(&self) -> bool {
+        self.preserve_condition_parentheses
+    }
+
+    /// Returns the configured value for [`indent_trailing_callback_body`]
+    pub fn indent_trailing_callback_body(&self) -> bool {
+        self.indent_trailing_callback_body
+    }
+
+    /// Returns the configured value for [`preserve_first_lines`]
+    pub fn preserve_first_lines(&self) -> usize {
+        self.preserve_first_lines
+    }
+
+    /// Returns the configured value for [`max_blank_lines_in_function`]
+    pub fn max_blank_lines_in_function(&self) -> usize {
+        self.max_blank_lines_in_function
+    }
+
+    /// Returns the configured value for [`hang_ternary_idiom`]
+    pub fn hang_ternary_idiom(&self) -> bool {
+        self.hang_ternary_idiom
+    }
+
+    /// Returns the configured value for [`spaces_around_type_assertion`]
+    pub fn spaces_around_type_assertion(&self) -> bool {
+        self.spaces_around_type_assertion
+    }
+
+    /// Returns the configured value for [`blank_line_after_requires`]
+    pub fn blank_line_after_requires(&self) -> bool {
+        self.blank_line_after_requires
+    }
+
+    /// Returns the configured value for [`align_table_name_keys`]
+    pub fn align_table_name_keys(&self) -> bool {
+        self.align_table_name_keys
+    }
+
+    /// Returns the configured value for [`normalize_string_line_endings`]
+    pub fn normalize_string_line_endings(&self) -> bool {
+        self.normalize_string_line_endings
+    }
+
+    /// Returns the configured value for [`max_inline_nesting_depth`]
+    pub fn max_inline_nesting_depth(&self) -> usize {
+        self.max_inline_nesting_depth
+    }
+
+    /// Returns the configured value for [`align_else_leading_comments`]
+    pub fn align_else_leading_comments(&self) -> bool {
+        self.align_else_leading_comments
+    }
+
+    /// Returns the configured value for [`verbatim_comment_prefixes`]
+    pub fn verbatim_comment_prefixes(&self) -> VerbatimCommentPrefixes {
+        self.verbatim_comment_prefixes
+    }
+
     /// Returns a new config with the given column width
     pub fn with_column_width(self, column_width: usize) -> Self {
         Self {
@@ -208,21 +761,339 @@ impl Config {
             ..self
         }
     }
+
+    /// Returns a new config with the given value for [`preserve_table_boundary_semicolon`]
+    pub fn with_preserve_table_boundary_semicolon(
+        self,
+        preserve_table_boundary_semicolon: bool,
+    ) -> Self {
+        Self {
+            preserve_table_boundary_semicolon,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`indent_else_clauses`]
+    pub fn with_indent_else_clauses(self, indent_else_clauses: bool) -> Self {
+        Self {
+            indent_else_clauses,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`align_table_expression_keys`]
+    pub fn with_align_table_expression_keys(self, align_table_expression_keys: bool) -> Self {
+        Self {
+            align_table_expression_keys,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`collapse_simple_statement`]
+    pub fn with_collapse_simple_statement(self, collapse_simple_statement: bool) -> Self {
+        Self {
+            collapse_simple_statement,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`merge_string_concatenation`]
+    pub fn with_merge_string_concatenation(self, merge_string_concatenation: bool) -> Self {
+        Self {
+            merge_string_concatenation,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`spaces_inside_parentheses`]
+    pub fn with_spaces_inside_parentheses(self, spaces_inside_parentheses: bool) -> Self {
+        Self {
+            spaces_inside_parentheses,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`max_inline_method_chain`]
+    pub fn with_max_inline_method_chain(self, max_inline_method_chain: usize) -> Self {
+        Self {
+            max_inline_method_chain,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`preserve_number_notation`]
+    pub fn with_preserve_number_notation(self, preserve_number_notation: bool) -> Self {
+        Self {
+            preserve_number_notation,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`space_after_anonymous_function`]
+    pub fn with_space_after_anonymous_function(self, space_after_anonymous_function: bool) -> Self {
+        Self {
+            space_after_anonymous_function,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`reindent_multiline_strings`]
+    pub fn with_reindent_multiline_strings(self, reindent_multiline_strings: bool) -> Self {
+        Self {
+            reindent_multiline_strings,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`parenthesise_mixed_and_or`]
+    pub fn with_parenthesise_mixed_and_or(self, parenthesise_mixed_and_or: bool) -> Self {
+        Self {
+            parenthesise_mixed_and_or,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`align_trailing_comments`]
+    pub fn with_align_trailing_comments(self, align_trailing_comments: bool) -> Self {
+        Self {
+            align_trailing_comments,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`whitespace_only`]
+    pub fn with_whitespace_only(self, whitespace_only: bool) -> Self {
+        Self {
+            whitespace_only,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`spaces_inside_index_brackets`]
+    pub fn with_spaces_inside_index_brackets(self, spaces_inside_index_brackets: bool) -> Self {
+        Self {
+            spaces_inside_index_brackets,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`max_inline_width`]
+    pub fn with_max_inline_width(self, max_inline_width: usize) -> Self {
+        Self {
+            max_inline_width,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`hug_table_argument`]
+    pub fn with_hug_table_argument(self, hug_table_argument: bool) -> Self {
+        Self {
+            hug_table_argument,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`attach_hanging_condition_keyword`]
+    pub fn with_attach_hanging_condition_keyword(
+        self,
+        attach_hanging_condition_keyword: bool,
+    ) -> Self {
+        Self {
+            attach_hanging_condition_keyword,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`inline_condition_trailing_comments`]
+    pub fn with_inline_condition_trailing_comments(
+        self,
+        inline_condition_trailing_comments: bool,
+    ) -> Self {
+        Self {
+            inline_condition_trailing_comments,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`prefer_local_function`]
+    pub fn with_prefer_local_function(self, prefer_local_function: bool) -> Self {
+        Self {
+            prefer_local_function,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`attach_trailing_callback`]
+    pub fn with_attach_trailing_callback(self, attach_trailing_callback: bool) -> Self {
+        Self {
+            attach_trailing_callback,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`max_input_size`]
+    pub fn with_max_input_size(self, max_input_size: usize) -> Self {
+        Self {
+            max_input_size,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`omit_single_field_trailing_sep`]
+    pub fn with_omit_single_field_trailing_sep(self, omit_single_field_trailing_sep: bool) -> Self {
+        Self {
+            omit_single_field_trailing_sep,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`preserve_condition_parentheses`]
+    pub fn with_preserve_condition_parentheses(self, preserve_condition_parentheses: bool) -> Self {
+        Self {
+            preserve_condition_parentheses,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`indent_trailing_callback_body`]
+    pub fn with_indent_trailing_callback_body(self, indent_trailing_callback_body: bool) -> Self {
+        Self {
+            indent_trailing_callback_body,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`preserve_first_lines`]
+    pub fn with_preserve_first_lines(self, preserve_first_lines: usize) -> Self {
+        Self {
+            preserve_first_lines,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`max_blank_lines_in_function`]
+    pub fn with_max_blank_lines_in_function(self, max_blank_lines_in_function: usize) -> Self {
+        Self {
+            max_blank_lines_in_function,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`hang_ternary_idiom`]
+    pub fn with_hang_ternary_idiom(self, hang_ternary_idiom: bool) -> Self {
+        Self {
+            hang_ternary_idiom,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`spaces_around_type_assertion`]
+    pub fn with_spaces_around_type_assertion(self, spaces_around_type_assertion: bool) -> Self {
+        Self {
+            spaces_around_type_assertion,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`blank_line_after_requires`]
+    pub fn with_blank_line_after_requires(self, blank_line_after_requires: bool) -> Self {
+        Self {
+            blank_line_after_requires,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`align_table_name_keys`]
+    pub fn with_align_table_name_keys(self, align_table_name_keys: bool) -> Self {
+        Self {
+            align_table_name_keys,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`normalize_string_line_endings`]
+    pub fn with_normalize_string_line_endings(self, normalize_string_line_endings: bool) -> Self {
+        Self {
+            normalize_string_line_endings,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`max_inline_nesting_depth`]
+    pub fn with_max_inline_nesting_depth(self, max_inline_nesting_depth: usize) -> Self {
+        Self {
+            max_inline_nesting_depth,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`align_else_leading_comments`]
+    pub fn with_align_else_leading_comments(self, align_else_leading_comments: bool) -> Self {
+        Self {
+            align_else_leading_comments,
+            ..self
+        }
+    }
+
+    /// Returns a new config with the given value for [`verbatim_comment_prefixes`]
+    pub fn with_verbatim_comment_prefixes(
+        self,
+        verbatim_comment_prefixes: VerbatimCommentPrefixes,
+    ) -> Self {
+        Self {
+            verbatim_comment_prefixes,
+            ..self
+        }
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
-            column_width: 120,
+            column_width: Config::DEFAULT_COLUMN_WIDTH,
             line_endings: LineEndings::Unix,
-            indent_type: IndentType::Tabs,
-            indent_width: 4,
-            quote_style: QuoteStyle::default(),
+            indent_type: Config::DEFAULT_INDENT_TYPE,
+            indent_width: Config::DEFAULT_INDENT_WIDTH,
+            quote_style: Config::DEFAULT_QUOTE_STYLE,
             no_call_parentheses: false,
             table_sep: TableSeparators::Comma,
             extra_sep_at_table_end: false,
             extra_spaces_inside_table: true,
             extra_space_in_empty_table: false,
+            preserve_table_boundary_semicolon: false,
+            indent_else_clauses: false,
+            align_table_expression_keys: false,
+            collapse_simple_statement: false,
+            merge_string_concatenation: false,
+            spaces_inside_parentheses: false,
+            max_inline_method_chain: usize::MAX,
+            preserve_number_notation: true,
+            space_after_anonymous_function: false,
+            reindent_multiline_strings: false,
+            parenthesise_mixed_and_or: false,
+            align_trailing_comments: false,
+            whitespace_only: false,
+            spaces_inside_index_brackets: false,
+            max_inline_width: usize::MAX,
+            hug_table_argument: true,
+            attach_hanging_condition_keyword: false,
+            inline_condition_trailing_comments: false,
+            prefer_local_function: false,
+            attach_trailing_callback: false,
+            max_input_size: usize::MAX,
+            omit_single_field_trailing_sep: false,
+            preserve_condition_parentheses: false,
+            indent_trailing_callback_body: false,
+            preserve_first_lines: 0,
+            max_blank_lines_in_function: 1,
+            hang_ternary_idiom: false,
+            spaces_around_type_assertion: true,
+            blank_line_after_requires: false,
+            align_table_name_keys: false,
+            normalize_string_line_endings: false,
+            max_inline_nesting_depth: usize::MAX,
+            align_else_leading_comments: false,
+            verbatim_comment_prefixes: VerbatimCommentPrefixes::default(),
         }
     }
 }
@@ -236,14 +1107,74 @@ pub enum OutputVerification {
     None,
 }
 
+/// A signal used to cancel formatting, such as when it is taking too long in an interactive setting.
+/// Formatting is aborted once the flag is set, or the deadline has passed, whichever happens first.
+///
+/// The signal is checked before formatting each statement, including those within nested blocks, so a single
+/// statement which is very expensive to format (e.g. a large table with no functions) is not interrupted.
+/// The default signal never aborts.
+#[derive(Clone, Debug, Default)]
+pub struct AbortSignal {
+    flag: Option<Arc<AtomicBool>>,
+    deadline: Option<Instant>,
+}
+
+impl AbortSignal {
+    /// Creates a new signal, which never aborts
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new signal which aborts once the given flag is set to `true`
+    pub fn with_flag(self, flag: Arc<AtomicBool>) -> Self {
+        Self {
+            flag: Some(flag),
+            ..self
+        }
+    }
+
+    /// Returns a new signal which aborts once the given deadline has passed
+    pub fn with_deadline(self, deadline: Instant) -> Self {
+        Self {
+            deadline: Some(deadline),
+            ..self
+        }
+    }
+
+    /// Returns a new signal which aborts once the given duration has passed from now
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    /// Whether formatting should be aborted
+    pub fn is_aborted(&self) -> bool {
+        matches!(&self.flag, Some(flag) if flag.load(Ordering::Relaxed))
+            || matches!(self.deadline, Some(deadline) if Instant::now() >= deadline)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum Error {
     /// The input AST has a parsing error.
     ParseError(full_moon::Error),
+    /// The input code is a single expression rather than a block of statements. Only returned by [`format_block_code`].
+    ExpressionNotBlock,
+    /// The input is larger than the configured [`Config::max_input_size`], so was not formatted.
+    /// Contains the size of the input and the maximum size, in bytes.
+    InputTooLarge(usize, usize),
     /// The output AST after formatting generated a parse error. This is a definite error.
     VerificationAstError(full_moon::Error),
     /// The output AST after formatting differs from the input AST.
     VerificationAstDifference,
+    /// Formatting was cancelled by the [`AbortSignal`]. Only returned by [`format_code_with_abort_signal`].
+    Aborted,
+    /// An error occurred whilst formatting code from the given file. Only returned by [`format_code_with_filename`].
+    FileError(String, Box<Error>),
+    /// An error occurred whilst reading or writing a file. Only returned by [`format_file`] and [`format_files`].
+    IoError(Arc<std::io::Error>),
+    /// Formatting a file panicked, which is a bug in StyLua. Contains the panic message. Only returned by [`format_files`],
+    /// so that one file panicking does not prevent the other files from being formatted.
+    Panicked(String),
 }
 
 impl std::fmt::Display for Error {
@@ -253,8 +1184,14 @@ impl std::fmt::Display for Error {
                 full_moon::Error::AstError(error) => write!(formatter, "error parsing: {}", error),
                 full_moon::Error::TokenizerError(error) => write!(formatter, "error parsing: {}", error),
             },
+            Error::ExpressionNotBlock => write!(formatter, "error parsing: expected a block of statements, but found an expression"),
+            Error::InputTooLarge(size, max_size) => write!(formatter, "input is too large to format: {} bytes exceeds the maximum of {} bytes", size, max_size),
             Error::VerificationAstError(error) => write!(formatter, "INTERNAL ERROR: Output AST generated a syntax error. Please report this at https://github.com/johnnymorganz/stylua/issues\n{}", error),
             Error::VerificationAstDifference => write!(formatter, "INTERNAL WARNING: Output AST may be different to input AST. Code correctness may have changed. Please examine the formatting diff and report any issues at https://github.com/johnnymorganz/stylua/issues"),
+            Error::Aborted => write!(formatter, "formatting was aborted"),
+            Error::FileError(filename, error) => write!(formatter, "{}: {}", filename, error),
+            Error::IoError(error) => write!(formatter, "{}", error),
+            Error::Panicked(message) => write!(formatter, "INTERNAL ERROR: formatting panicked: {}. Please report this at https://github.com/johnnymorganz/stylua/issues", message),
         }
     }
 }
@@ -268,6 +1205,228 @@ pub fn format_code(
     range: Option<Range>,
     verify_output: OutputVerification,
 ) -> Result<String, Error> {
+    format_code_with_abort_signal(code, config, range, verify_output, &AbortSignal::default())
+}
+
+/// Formats given Lua code, returning [`Error::Aborted`] if the given signal is triggered before formatting is complete.
+/// This prevents a pathological input from blocking a thread indefinitely, e.g. within a language server.
+/// See [`AbortSignal`] for how often the signal is checked.
+pub fn format_code_with_abort_signal(
+    code: &str,
+    config: Config,
+    range: Option<Range>,
+    verify_output: OutputVerification,
+    abort_signal: &AbortSignal,
+) -> Result<String, Error> {
+    let (config, header, code) = prepare_code(code, config)?;
+    let range = range.map(|range| range.shift_back(header.input_len()));
+
+    let input_ast = match full_moon::parse(code) {
+        Ok(ast) => ast,
+        Err(error) => {
+            return Err(Error::ParseError(error));
+        }
+    };
+
+    let output =
+        format_ast_with_abort_signal(input_ast, config, range, verify_output, abort_signal)?;
+    Ok(header.prepend(&output, config))
+}
+
+/// Returns [`Error::InputTooLarge`] if the code is larger than the configured maximum input size
+fn check_input_size(code: &str, config: Config) -> Result<(), Error> {
+    if code.len() > config.max_input_size {
+        Err(Error::InputTooLarge(code.len(), config.max_input_size))
+    } else {
+        Ok(())
+    }
+}
+
+/// Lines at the start of the code which are not formatted: the configured number of preserved lines, followed by a
+/// shebang line if present. These are removed before parsing, and added back to the start of the formatted output.
+struct Header<'a> {
+    preserved_lines: &'a str,
+    shebang: Option<&'a str>,
+    input_len: usize,
+}
+
+impl<'a> Header<'a> {
+    /// Splits the header from the start of the code, returning it and the rest of the code to format
+    fn split(code: &'a str, config: Config) -> (Self, &'a str) {
+        let (preserved_lines, rest) = split_first_lines(code, config.preserve_first_lines);
+        let (shebang, rest) = split_shebang(rest);
+        let header = Header {
+            preserved_lines,
+            shebang,
+            input_len: code.len() - rest.len(),
+        };
+        (header, rest)
+    }
+
+    /// The number of bytes the header took up in the input code
+    fn input_len(&self) -> usize {
+        self.input_len
+    }
+
+    /// Adds the header back to the start of the formatted output
+    fn prepend(&self, output: &str, config: Config) -> String {
+        match self.shebang {
+            Some(shebang) => format!(
+                "{}{}{}{}",
+                self.preserved_lines,
+                shebang,
+                context::line_ending_character(config.line_endings),
+                output
+            ),
+            None => format!("{}{}", self.preserved_lines, output),
+        }
+    }
+}
+
+/// The steps shared by every entry point which formats code: checks the input size, resolves the config to use, and
+/// splits off the [`Header`]. Returns the resolved config, the header, and the rest of the code to parse and format.
+fn prepare_code(code: &str, config: Config) -> Result<(Config, Header<'_>, &str), Error> {
+    check_input_size(code, config)?;
+    let config = resolve_config(config);
+    let (header, code) = Header::split(code, config);
+    Ok((config, header, code))
+}
+
+/// Splits the given number of lines from the start of the code. Returns these lines, including their line endings, and the
+/// rest of the code.
+fn split_first_lines(code: &str, lines: usize) -> (&str, &str) {
+    if lines == 0 {
+        return ("", code);
+    }
+
+    match code.match_indices('\n').nth(lines - 1) {
+        Some((index, _)) => code.split_at(index + 1),
+        None => (code, ""),
+    }
+}
+
+/// Splits a shebang line (e.g. `#!/usr/bin/env lua`) from the start of the code, if present.
+/// Returns the shebang line, without its line ending, and the rest of the code.
+fn split_shebang(code: &str) -> (Option<&str>, &str) {
+    if !code.starts_with("#!") {
+        return (None, code);
+    }
+
+    let (line, rest) = match code.find('\n') {
+        Some(index) => (&code[..index], &code[index + 1..]),
+        None => (code, ""),
+    };
+
+    (Some(line.trim_end_matches('\r')), rest)
+}
+
+/// Formats given Lua code, returning `None` if the output is identical to `previous_output`, such as the contents of a
+/// file written by a previous run. This lets build systems skip writing unchanged output.
+/// The output is compared exactly, in the same way as [`format_file`] determines whether a file has changed, so output
+/// which only differs in its line endings (e.g. after changing [`Config::line_endings`]) is returned.
+pub fn format_code_if_changed(
+    code: &str,
+    previous_output: &str,
+    config: Config,
+) -> Result<Option<String>, Error> {
+    let output = format_code(code, config, None, OutputVerification::None)?;
+    if output == previous_output {
+        Ok(None)
+    } else {
+        Ok(Some(output))
+    }
+}
+
+/// Formats given Lua code as a block of statements at the top level, without range or output verification.
+/// This is useful for snippets, such as those entered into a REPL, which do not need to be wrapped in a function to
+/// be formatted. If the code is a single expression rather than a block, [`Error::ExpressionNotBlock`] is returned.
+pub fn format_block_code(code: &str, config: Config) -> Result<String, Error> {
+    let (config, header, code) = prepare_code(code, config)?;
+
+    let input_ast = match full_moon::parse(code) {
+        Ok(ast) => ast,
+        Err(error) => {
+            // An expression is not a valid block, but can be returned from one
+            if full_moon::parse(&format!("return {}", code)).is_ok() {
+                return Err(Error::ExpressionNotBlock);
+            }
+            return Err(Error::ParseError(error));
+        }
+    };
+
+    let output = format_ast(input_ast, config, None, OutputVerification::None)?;
+    Ok(header.prepend(&output, config))
+}
+
+/// Formats given Lua code, also returning a map from each statement in the output back to the statement in the input
+/// it was formatted from. This is useful for editor integrations to keep the cursor or selection on the same code
+/// after formatting.
+///
+/// The map contains an entry for every statement, including those within nested blocks, in the order they appear.
+/// Statements within the lines kept by [`Config::preserve_first_lines`] are not formatted, so are not included.
+pub fn format_code_with_sourcemap(
+    code: &str,
+    config: Config,
+    range: Option<Range>,
+    verify_output: OutputVerification,
+) -> Result<(String, Vec<SourceMapping>), Error> {
+    let (config, header, code) = prepare_code(code, config)?;
+    let range = range.map(|range| range.shift_back(header.input_len()));
+
+    let input_ast = match full_moon::parse(code) {
+        Ok(ast) => ast,
+        Err(error) => {
+            return Err(Error::ParseError(error));
+        }
+    };
+    let input_ast_for_mapping = input_ast.to_owned();
+
+    let output = format_ast(input_ast, config, range, verify_output)?;
+    let output_ast = match full_moon::parse(&output) {
+        Ok(ast) => ast,
+        Err(error) => {
+            return Err(Error::VerificationAstError(error));
+        }
+    };
+
+    let mappings = sourcemap::statement_mappings(&input_ast_for_mapping, &output_ast)
+        .ok_or(Error::VerificationAstDifference)?;
+
+    // The ranges are relative to the code without its header, so shift them back to be relative to the full code
+    let formatted = header.prepend(&output, config);
+    let input_offset = header.input_len();
+    let output_offset = formatted.len() - output.len();
+    let mappings = mappings
+        .into_iter()
+        .map(|mapping| SourceMapping {
+            input: mapping.input.start + input_offset..mapping.input.end + input_offset,
+            output: mapping.output.start + output_offset..mapping.output.end + output_offset,
+        })
+        .collect();
+
+    Ok((formatted, mappings))
+}
+
+/// Formats given Lua code, first reordering its top level statements using the provided comparator.
+/// The comparator is given the code of two statements, without any leading or trailing comments and whitespace,
+/// and should return how they are ordered. The sort is stable, so statements which compare equal keep their original order.
+///
+/// Comments attached before a statement move with it. Only the top level statements are reordered, and any
+/// final `return` or `break` statement always stays last. When verifying the output, it is compared against the
+/// reordered input, so it is up to the comparator to only produce orderings which preserve meaning.
+pub fn format_code_with_reorder<F>(
+    code: &str,
+    config: Config,
+    range: Option<Range>,
+    verify_output: OutputVerification,
+    mut compare: F,
+) -> Result<String, Error>
+where
+    F: FnMut(&str, &str) -> std::cmp::Ordering,
+{
+    let (config, header, code) = prepare_code(code, config)?;
+    let range = range.map(|range| range.shift_back(header.input_len()));
+
     let input_ast = match full_moon::parse(code) {
         Ok(ast) => ast,
         Err(error) => {
@@ -275,6 +1434,66 @@ pub fn format_code(
         }
     };
 
+    let block = input_ast.nodes();
+    let mut stmts: Vec<(String, (Stmt, Option<TokenReference>))> = block
+        .stmts_with_semicolon()
+        .map(|stmt| (stripped_stmt_string(&stmt.0), stmt.to_owned()))
+        .collect();
+    stmts.sort_by(|(a, _), (b, _)| compare(a, b));
+
+    let block = block
+        .to_owned()
+        .with_stmts(stmts.into_iter().map(|(_, stmt)| stmt).collect());
+    let input_ast = input_ast.with_nodes(block);
+
+    let output = format_ast(input_ast, config, range, verify_output)?;
+    Ok(header.prepend(&output, config))
+}
+
+/// Returns the code of a statement, excluding the leading trivia of its first token and the trailing trivia of its last token
+fn stripped_stmt_string(stmt: &Stmt) -> String {
+    fn trivia_len<'a>(trivia: impl Iterator<Item = &'a Token>) -> usize {
+        trivia.map(|token| token.to_string().len()).sum()
+    }
+
+    let string = stmt.to_string();
+    let leading_len = stmt
+        .tokens()
+        .next()
+        .map_or(0, |token| trivia_len(token.leading_trivia()));
+    let trailing_len = stmt
+        .tokens()
+        .last()
+        .map_or(0, |token| trivia_len(token.trailing_trivia()));
+
+    string[leading_len..string.len() - trailing_len].to_owned()
+}
+
+/// Formats the given parsed AST, verifying the output against it if required
+fn format_ast(
+    input_ast: Ast,
+    config: Config,
+    range: Option<Range>,
+    verify_output: OutputVerification,
+) -> Result<String, Error> {
+    format_ast_with_abort_signal(
+        input_ast,
+        config,
+        range,
+        verify_output,
+        &AbortSignal::default(),
+    )
+}
+
+/// Formats the given parsed AST, verifying the output against it if required, and stopping early if the given signal
+/// is triggered
+fn format_ast_with_abort_signal(
+    input_ast: Ast,
+    config: Config,
+    range: Option<Range>,
+    verify_output: OutputVerification,
+    abort_signal: &AbortSignal,
+) -> Result<String, Error> {
     // Clone the input AST only if we are verifying, to later use for checking
     let input_ast_for_verification = if let OutputVerification::Full = verify_output {
         Some(input_ast.to_owned())
@@ -283,7 +1502,9 @@ pub fn format_code(
     };
 
     let code_formatter = formatters::CodeFormatter::new(config, range);
-    let ast = code_formatter.format(input_ast);
+    let ast = code_formatter
+        .format_with_abort_signal(input_ast, abort_signal)
+        .ok_or(Error::Aborted)?;
     let output = full_moon::print(&ast);
 
     // If we are verifying, reparse the output then check it matches the original input
@@ -295,7 +1516,7 @@ pub fn format_code(
             }
         };
 
-        let mut ast_verifier = verify_ast::AstVerifier::new();
+        let mut ast_verifier = verify_ast::AstVerifier::new(config);
         if !ast_verifier.compare(input_ast, reparsed_output) {
             return Err(Error::VerificationAstDifference);
         }
@@ -303,3 +1524,106 @@ pub fn format_code(
 
     Ok(output)
 }
+
+/// Formats given Lua code, which originated from the file with the given name.
+/// This is useful when the code did not come directly from the file (e.g. it was piped through stdin), but its path is known.
+/// If a filename is provided, any error is wrapped in [`Error::FileError`] so that it can be reported against the file.
+///
+/// The filename does not otherwise affect formatting. Note that the dialect of Lua parsed is determined by the
+/// features StyLua was compiled with, so the file extension cannot currently be used to select one.
+pub fn format_code_with_filename(
+    code: &str,
+    filename: Option<&str>,
+    config: Config,
+    range: Option<Range>,
+    verify_output: OutputVerification,
+) -> Result<String, Error> {
+    format_code(code, config, range, verify_output).map_err(|error| match filename {
+        Some(filename) => Error::FileError(filename.to_owned(), Box::new(error)),
+        None => error,
+    })
+}
+
+/// Formats the Lua file at the given path in place, only writing to it if formatting changed its contents.
+/// Returns whether the file was changed. Any error is wrapped in [`Error::FileError`] with the path of the file.
+pub fn format_file(
+    path: &Path,
+    config: Config,
+    verify_output: OutputVerification,
+) -> Result<bool, Error> {
+    let filename = path.display().to_string();
+    let io_error = |error| {
+        Error::FileError(
+            filename.to_owned(),
+            Box::new(Error::IoError(Arc::new(error))),
+        )
+    };
+
+    let contents = std::fs::read_to_string(path).map_err(io_error)?;
+    let formatted_contents =
+        format_code_with_filename(&contents, Some(&filename), config, None, verify_output)?;
+
+    if formatted_contents == contents {
+        return Ok(false);
+    }
+
+    std::fs::write(path, formatted_contents).map_err(io_error)?;
+    Ok(true)
+}
+
+/// Formats each of the Lua files at the given paths in place, as with [`format_file`].
+/// Returns the result of formatting each file, in the same order as the paths were given. An error in one file
+/// does not prevent the other files from being formatted.
+///
+/// The files are formatted concurrently by a pool with one worker thread per logical core, so no more than that many
+/// files are formatted at once, however many paths are given.
+pub fn format_files<P: AsRef<Path>>(
+    paths: &[P],
+    config: Config,
+    verify_output: OutputVerification,
+) -> Vec<(PathBuf, Result<bool, Error>)> {
+    let pool = threadpool::ThreadPool::new(num_cpus::get());
+    let (sender, receiver) = crossbeam_channel::unbounded();
+
+    for (index, path) in paths.iter().enumerate() {
+        let path = path.as_ref().to_path_buf();
+        let sender = sender.clone();
+        pool.execute(move || {
+            // Catch any panic, so that a result is still sent for this file
+            let result = std::panic::catch_unwind(|| format_file(&path, config, verify_output))
+                .unwrap_or_else(|payload| {
+                    let message = payload
+                        .downcast_ref::<&str>()
+                        .map(|message| message.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_else(|| String::from("unknown panic"));
+                    Err(Error::FileError(
+                        path.display().to_string(),
+                        Box::new(Error::Panicked(message)),
+                    ))
+                });
+            sender
+                .send((index, path, result))
+                .expect("could not send format result");
+        });
+    }
+
+    // Drop our sender so that the receiver finishes once all files have been formatted
+    drop(sender);
+
+    let mut results: Vec<_> = receiver.iter().collect();
+    results.sort_by_key(|(index, _, _)| *index);
+    results
+        .into_iter()
+        .map(|(_, path, result)| (path, result))
+        .collect()
+}
+
+/// Formats the given Lua code using the default configuration and full output verification,
+/// discarding the output and only returning the result of verification.
+/// This is intended to be used as a fuzzing target: any [`Error::VerificationAstError`] or
+/// [`Error::VerificationAstDifference`] returned indicates a bug in the formatter.
+#[cfg(feature = "fuzzing")]
+pub fn fuzz_check(code: &str) -> Result<(), Error> {
+    format_code(code, Config::default(), None, OutputVerification::Full).map(|_| ())
+}
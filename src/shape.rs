@@ -3,7 +3,7 @@ use std::fmt::Display;
 use std::ops::Add;
 
 /// A struct representing indentation level of the current code
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Indent {
     /// How many characters a single indent level represents. This is inferred from the configuration
     indent_width: usize,
@@ -87,7 +87,7 @@ impl Indent {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Shape {
     /// The current indentation level
     indent: Indent,
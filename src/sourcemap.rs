@@ -0,0 +1,70 @@
+use full_moon::{
+    ast::{Ast, LastStmt, Stmt},
+    node::Node,
+    visitors::Visitor,
+};
+use std::ops::Range;
+
+/// A mapping between a statement in the formatted output and the statement in the input it was formatted from.
+/// Ranges are byte offsets, and exclude any leading or trailing comments and whitespace of the statement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceMapping {
+    /// The byte range of the statement in the input code
+    pub input: Range<usize>,
+    /// The byte range of the statement in the formatted output
+    pub output: Range<usize>,
+}
+
+/// Collects the byte range of every statement in an AST, including those in nested blocks, in the order they appear
+#[derive(Default)]
+struct StatementRanges {
+    ranges: Vec<Option<Range<usize>>>,
+}
+
+impl StatementRanges {
+    fn push(&mut self, node: &impl Node) {
+        self.ranges
+            .push(node.range().map(|(start, end)| start.bytes()..end.bytes()));
+    }
+}
+
+impl Visitor for StatementRanges {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        self.push(stmt);
+    }
+
+    fn visit_last_stmt(&mut self, last_stmt: &LastStmt) {
+        self.push(last_stmt);
+    }
+}
+
+fn statement_ranges(ast: &Ast) -> Vec<Option<Range<usize>>> {
+    let mut collector = StatementRanges::default();
+    collector.visit_ast(ast);
+    collector.ranges
+}
+
+/// Pairs up every statement in the input AST with the corresponding statement in the reparsed output AST.
+/// Formatting never adds or removes statements, so they are matched up in order. Returns `None` if the
+/// number of statements differ, as the output can no longer be mapped back to the input.
+pub fn statement_mappings(input_ast: &Ast, output_ast: &Ast) -> Option<Vec<SourceMapping>> {
+    let input_ranges = statement_ranges(input_ast);
+    let output_ranges = statement_ranges(output_ast);
+
+    if input_ranges.len() != output_ranges.len() {
+        return None;
+    }
+
+    Some(
+        input_ranges
+            .into_iter()
+            .zip(output_ranges)
+            .filter_map(|(input, output)| {
+                Some(SourceMapping {
+                    input: input?,
+                    output: output?,
+                })
+            })
+            .collect(),
+    )
+}
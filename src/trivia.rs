@@ -0,0 +1,25 @@
+//! Utilities for inspecting and manipulating the trivia (whitespace and comments) attached to full_moon nodes.
+//!
+//! These are the same helpers StyLua uses internally whilst formatting, exposed so that tools building on top of
+//! StyLua and full_moon do not need to reimplement them.
+
+pub use crate::formatters::trivia::{
+    strip_leading_trivia, strip_trailing_trivia, strip_trivia, FormatTriviaType,
+    UpdateLeadingTrivia, UpdateTrailingTrivia, UpdateTrivia,
+};
+pub use crate::formatters::trivia_util::{
+    contains_comments, token_contains_comments, token_contains_leading_comments,
+    token_contains_trailing_comments, trivia_is_comment, trivia_is_newline, trivia_is_whitespace,
+};
+
+/// Returns the printed width of a node, excluding its leading and trailing trivia. This is the same measurement the
+/// formatter uses when checking whether a node fits within the column width.
+///
+/// The width is counted in bytes rather than display columns, so a non-ASCII character counts as more than one. Any
+/// newlines within the node are counted as-is, so this is only the width of a line for a node printed on a single line.
+pub fn printed_width<T>(node: &T) -> usize
+where
+    T: UpdateLeadingTrivia + UpdateTrailingTrivia + std::fmt::Display,
+{
+    strip_trivia(node).to_string().len()
+}
@@ -2,18 +2,65 @@ use full_moon::{
     ast::{
         punctuated::{Pair, Punctuated},
         span::ContainedSpan,
-        Ast, Block, Expression, FunctionArgs, TableConstructor, Value,
+        Ast, BinOp, Block, Expression, FunctionArgs, LastStmt, Stmt, TableConstructor, Value,
     },
     node::Node,
     tokenizer::{StringLiteralQuoteType, Token, TokenReference, TokenType},
     visitors::VisitorMut,
 };
 
-pub struct AstVerifier {}
+use crate::{
+    context::line_ending_character,
+    formatters::{functions::local_assignment_to_local_function, general::normalize_line_endings},
+    Config,
+};
+
+/// Verifies that the AST of formatted output is equivalent to the AST of the original input.
+///
+/// Before comparing, both ASTs are normalised so that the following transforms, which are known to be safe, are not
+/// reported as differences:
+/// - removing semicolons after statements
+/// - rewriting `local f = function() end` as `local function f() end`, if [`Config::prefer_local_function`] is enabled
+/// - changing table field separators and trailing separators
+/// - adding or removing parentheses around call arguments, i.e. `f "x"` and `f { }`
+/// - adding or removing parentheses around expressions, such as in conditions
+/// - merging concatenations of string literals, if [`Config::merge_string_concatenation`] is enabled
+/// - changing the notation of number literals
+/// - changing string quotes and escapes
+/// - reindenting multiline strings, if [`Config::reindent_multiline_strings`] is enabled
+/// - normalising the line endings of multiline strings, if [`Config::normalize_string_line_endings`] is enabled
+#[derive(Default)]
+pub struct AstVerifier {
+    config: Config,
+}
+
+/// The first structural difference found between two ASTs when verifying them
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The kind of top level node the difference was found in, e.g. `LocalAssignment`
+    pub node_kind: &'static str,
+    /// The first differing token from the input AST, or `None` if the input had no more tokens
+    pub expected: Option<String>,
+    /// The first differing token from the output AST, or `None` if the output had no more tokens
+    pub actual: Option<String>,
+}
+
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "mismatch in {}: expected {}, found {}",
+            self.node_kind,
+            self.expected.as_deref().unwrap_or("<nothing>"),
+            self.actual.as_deref().unwrap_or("<nothing>")
+        )
+    }
+}
 
 impl AstVerifier {
-    pub fn new() -> Self {
-        Self {}
+    /// Creates a verifier for output formatted with the given config
+    pub fn new(config: Config) -> Self {
+        Self { config }
     }
 
     /// Compares two ASTs to see if they are similar.
@@ -24,11 +71,134 @@ impl AstVerifier {
 
         massaged_input.similar(&massaged_output)
     }
+
+    /// Compares two ASTs in the same way as [`AstVerifier::compare`], but returns the first difference found.
+    /// Statements are compared in order, and the first differing token within the first differing statement is reported.
+    pub fn compare_detailed(
+        &mut self,
+        input_ast: Ast,
+        reparsed_output: Ast,
+    ) -> Result<(), Mismatch> {
+        let massaged_input = self.visit_ast(input_ast);
+        let massaged_output = self.visit_ast(reparsed_output);
+
+        if massaged_input.similar(&massaged_output) {
+            return Ok(());
+        }
+
+        Err(find_block_mismatch(
+            massaged_input.nodes(),
+            massaged_output.nodes(),
+        ))
+    }
+}
+
+fn stmt_kind(stmt: &Stmt) -> &'static str {
+    match stmt {
+        Stmt::Assignment(_) => "Assignment",
+        Stmt::Do(_) => "Do",
+        Stmt::FunctionCall(_) => "FunctionCall",
+        Stmt::FunctionDeclaration(_) => "FunctionDeclaration",
+        Stmt::GenericFor(_) => "GenericFor",
+        Stmt::If(_) => "If",
+        Stmt::LocalAssignment(_) => "LocalAssignment",
+        Stmt::LocalFunction(_) => "LocalFunction",
+        Stmt::NumericFor(_) => "NumericFor",
+        Stmt::Repeat(_) => "Repeat",
+        Stmt::While(_) => "While",
+        #[cfg(feature = "luau")]
+        Stmt::CompoundAssignment(_) => "CompoundAssignment",
+        #[cfg(feature = "luau")]
+        Stmt::ExportedTypeDeclaration(_) => "ExportedTypeDeclaration",
+        #[cfg(feature = "luau")]
+        Stmt::TypeDeclaration(_) => "TypeDeclaration",
+        #[cfg(feature = "lua52")]
+        Stmt::Goto(_) => "Goto",
+        #[cfg(feature = "lua52")]
+        Stmt::Label(_) => "Label",
+        _ => "Stmt",
+    }
 }
 
+fn last_stmt_kind(last_stmt: &LastStmt) -> &'static str {
+    match last_stmt {
+        LastStmt::Break(_) => "Break",
+        LastStmt::Return(_) => "Return",
+        #[cfg(feature = "luau")]
+        LastStmt::Continue(_) => "Continue",
+        _ => "LastStmt",
+    }
+}
+
+/// Finds the first differing token between two nodes
+fn find_token_mismatch<T: Node + std::fmt::Display>(
+    node_kind: &'static str,
+    expected: T,
+    actual: T,
+) -> Mismatch {
+    let mut expected_tokens = expected.tokens();
+    let mut actual_tokens = actual.tokens();
+
+    loop {
+        match (expected_tokens.next(), actual_tokens.next()) {
+            (Some(expected), Some(actual)) if expected.similar(actual) => continue,
+            (None, None) => {
+                // All the tokens match, so the difference is purely structural. Report the whole nodes instead
+                return Mismatch {
+                    node_kind,
+                    expected: Some(expected.to_string().trim().to_owned()),
+                    actual: Some(actual.to_string().trim().to_owned()),
+                };
+            }
+            (expected, actual) => {
+                return Mismatch {
+                    node_kind,
+                    expected: expected.map(|token| token.token().to_string()),
+                    actual: actual.map(|token| token.token().to_string()),
+                }
+            }
+        }
+    }
+}
+
+/// Finds the first differing statement between two blocks, and the first differing token within it
+fn find_block_mismatch(expected: &Block, actual: &Block) -> Mismatch {
+    let mut expected_stmts = expected.stmts();
+    let mut actual_stmts = actual.stmts();
+
+    loop {
+        match (expected_stmts.next(), actual_stmts.next()) {
+            (Some(expected), Some(actual)) if expected.similar(actual) => continue,
+            (Some(expected), Some(actual)) if stmt_kind(expected) == stmt_kind(actual) => {
+                return find_token_mismatch(stmt_kind(expected), expected, actual)
+            }
+            (None, None) => break,
+            (expected, actual) => {
+                return Mismatch {
+                    node_kind: "Block",
+                    expected: expected.map(|stmt| stmt.to_string().trim().to_owned()),
+                    actual: actual.map(|stmt| stmt.to_string().trim().to_owned()),
+                }
+            }
+        }
+    }
+
+    match (expected.last_stmt(), actual.last_stmt()) {
+        (Some(expected), Some(actual)) if last_stmt_kind(expected) == last_stmt_kind(actual) => {
+            find_token_mismatch(last_stmt_kind(expected), expected, actual)
+        }
+        (expected, actual) => Mismatch {
+            node_kind: "Block",
+            expected: expected.map(|last_stmt| last_stmt.to_string().trim().to_owned()),
+            actual: actual.map(|last_stmt| last_stmt.to_string().trim().to_owned()),
+        },
+    }
+}
+
+/// Removes all layers of parentheses around an expression
 fn remove_parentheses(expression: Expression) -> Expression {
     match expression {
-        Expression::Parentheses { expression, .. } => *expression,
+        Expression::Parentheses { expression, .. } => remove_parentheses(*expression),
         Expression::Value { value, .. } => Expression::Value {
             value: match *value {
                 Value::ParenthesesExpression(expression) => return remove_parentheses(expression),
@@ -41,6 +211,70 @@ fn remove_parentheses(expression: Expression) -> Expression {
     }
 }
 
+/// Collects the raw contents of every quoted string literal in a chain of `..` concatenations, ignoring parentheses.
+/// Returns `None` if any operand of the chain is not a quoted string literal.
+fn collect_concatenated_literals(
+    expression: &Expression,
+    literals: &mut Vec<String>,
+) -> Option<()> {
+    match remove_parentheses(expression.to_owned()) {
+        Expression::BinaryOperator {
+            lhs,
+            binop: BinOp::TwoDots(_),
+            rhs,
+        } => {
+            collect_concatenated_literals(&lhs, literals)?;
+            collect_concatenated_literals(&rhs, literals)
+        }
+        Expression::Value { value, .. } => match *value {
+            Value::String(token) => match token.token_type() {
+                TokenType::StringLiteral {
+                    literal,
+                    quote_type: StringLiteralQuoteType::Double | StringLiteralQuoteType::Single,
+                    ..
+                } => {
+                    literals.push(literal.to_string());
+                    Some(())
+                }
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Folds a concatenation consisting purely of quoted string literals into a single string literal
+fn fold_string_concatenation(expression: Expression) -> Expression {
+    if !matches!(
+        expression,
+        Expression::BinaryOperator {
+            binop: BinOp::TwoDots(_),
+            ..
+        }
+    ) {
+        return expression;
+    }
+
+    let mut literals = Vec::new();
+    match collect_concatenated_literals(&expression, &mut literals) {
+        Some(()) => Expression::Value {
+            value: Box::new(Value::String(TokenReference::new(
+                vec![],
+                Token::new(TokenType::StringLiteral {
+                    literal: literals.concat().into(),
+                    multi_line: None,
+                    quote_type: StringLiteralQuoteType::Double,
+                }),
+                vec![],
+            ))),
+            #[cfg(feature = "luau")]
+            type_assertion: None,
+        },
+        None => expression,
+    }
+}
+
 // Massages the AST so that structures we have changed in Nodes remain constant.
 // Note, the massaged AST may not actually be valid syntax if we print it back out, but we have already checked
 // the validity of the output, so any invalid syntax output would already have been flagged.
@@ -60,6 +294,24 @@ impl VisitorMut for AstVerifier {
         node.with_stmts(stmts).with_last_stmt(last_stmt)
     }
 
+    fn visit_stmt(&mut self, node: Stmt) -> Stmt {
+        // If enabled, we may rewrite `local f = function() end` as `local function f() end`
+        // We will convert all such assignments which can be safely rewritten into local functions
+        if !self.config.prefer_local_function() || self.config.whitespace_only() {
+            return node;
+        }
+
+        match &node {
+            Stmt::LocalAssignment(local_assignment) => {
+                match local_assignment_to_local_function(local_assignment) {
+                    Some(local_function) => Stmt::LocalFunction(local_function),
+                    None => node,
+                }
+            }
+            _ => node,
+        }
+    }
+
     fn visit_table_constructor(&mut self, node: TableConstructor) -> TableConstructor {
         // We change semicolon field separators to commas
         // We will replace all field separators with commas, and include a trailing comma
@@ -116,7 +368,13 @@ impl VisitorMut for AstVerifier {
     fn visit_expression(&mut self, node: Expression) -> Expression {
         // There are places where we remove parentheses.
         // TODO: is this too eager? will we lose out in finding differences by doing this?
-        remove_parentheses(node)
+        // If enabled, we may also merge concatenations of string literals into a single literal.
+        // We will fold all such concatenations, so that the merged and unmerged forms compare the same
+        let node = remove_parentheses(node);
+        match self.config.merge_string_concatenation() {
+            true => fold_string_concatenation(node),
+            false => node,
+        }
     }
 
     fn visit_number(&mut self, token: Token) -> Token {
@@ -153,17 +411,48 @@ impl VisitorMut for AstVerifier {
 
     fn visit_string_literal(&mut self, token: Token) -> Token {
         // We change the string quotes of our progrem.
-        // Convert all string literals to brackets quotes, and remove any escapes.
+        // Convert all quoted string literals to double quotes, and remove any escapes. Long bracket strings keep their
+        // quotes, as a bracket string with no `multi_line` level cannot be printed when reporting a mismatch
         let token_type = match token.token_type() {
             TokenType::StringLiteral {
                 literal,
                 multi_line,
-                ..
-            } => TokenType::StringLiteral {
-                literal: literal.to_owned().replace("\\", "").into(),
-                multi_line: multi_line.to_owned(),
-                quote_type: StringLiteralQuoteType::Brackets,
-            },
+                quote_type,
+            } => {
+                // If enabled, the contents of long bracket strings may be reindented and have their line endings
+                // normalised, so remove the indentation of each line and normalise the line endings
+                let reindent_multiline_strings = self.config.reindent_multiline_strings();
+                let literal = match quote_type {
+                    StringLiteralQuoteType::Brackets => {
+                        let literal = literal
+                            .split('\n')
+                            .map(|line| match reindent_multiline_strings {
+                                true => line.trim_start_matches([' ', '\t']),
+                                false => line,
+                            })
+                            .collect::<Vec<_>>()
+                            .join("\n");
+
+                        match self.config.normalize_string_line_endings() {
+                            true => normalize_line_endings(
+                                &literal,
+                                &line_ending_character(self.config.line_endings()),
+                            ),
+                            false => literal,
+                        }
+                    }
+                    _ => literal.to_owned().replace("\\", ""),
+                };
+
+                TokenType::StringLiteral {
+                    literal: literal.into(),
+                    multi_line: multi_line.to_owned(),
+                    quote_type: match quote_type {
+                        StringLiteralQuoteType::Brackets => StringLiteralQuoteType::Brackets,
+                        _ => StringLiteralQuoteType::Double,
+                    },
+                }
+            }
             _ => unreachable!(),
         };
 
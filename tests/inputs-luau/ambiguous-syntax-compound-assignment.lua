@@ -0,0 +1,9 @@
+local x = 1
+x += foo;
+(bar)()
+x -= 1;
+(t).y = 2
+local a = b;
+(t).y += 1
+(t).y ..= "a";
+print(x)
@@ -0,0 +1,11 @@
+local function build()
+	local output = ""
+	output ..= "this is a rather long string literal which is being appended to the output string" .. anotherStringVariable
+	output..="short"
+	self.someLongPropertyName ..= firstPieceOfTheString .. secondPieceOfTheString .. thirdPieceOfTheString .. fourthPiece
+	self.total  +=  calculateSomethingExpensive(argumentNumberOne, argumentNumberTwo, argumentNumberThree, argumentNumberFour)
+	self.total -= aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa * bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb
+	output ..= -- separator
+		", "
+	return output
+end
@@ -0,0 +1,10 @@
+local function foo(a: number, ...: string) end
+
+local function bar(aaaaaaaaaaaaaaaaaaaaaaaaaaaaaa: number, bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb: string, cccccccccc: boolean, ...: any)
+	return ...
+end
+
+local function baz(a: number, -- first parameter
+	...: string -- remaining arguments
+)
+end
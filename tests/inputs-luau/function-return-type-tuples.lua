@@ -0,0 +1,13 @@
+local function single(): number return 1 end
+local function tuple(): (number, string) return 1, "" end
+local function variadic(): ...number return 1 end
+local function tupleVariadic(): (number, ...string) return 1 end
+local function generic<T...>(): T... end
+
+local function someLongFunctionName(parameterOne: number): (SomeVeryLongTypeName, AnotherVeryLongTypeName, YetAnotherVeryLongTypeName, ...YetAnotherType)
+	return 1
+end
+
+local function foo(): (SomeVeryLongTypeName, AnotherVeryLongTypeName, YetAnotherVeryLongTypeName, AndAnotherLongTypeName, ...YetAnotherType)
+	return 1
+end
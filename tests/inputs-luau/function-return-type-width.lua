@@ -0,0 +1,27 @@
+-- Short return types stay inline with the parameters
+local function getValue(): number
+	return 1
+end
+
+local function convert(value: T): R
+	return value
+end
+
+local function getDefaultConfigurationForTheApplicationWithAVeryLongName(): ApplicationConfigurationType
+	return {}
+end
+
+local callback = function(): number return 1 end
+
+-- Long return types
+local function getValueWithAnExtremelyLongFunctionNameThatGoesOnAndOn(): SomeModule.SomeVeryLongTypeName<string, number>
+	return nil
+end
+
+local function foo(a: number): { alpha: number, beta: string, gamma: boolean, delta: { number }, epsilon: string, zeta: number }
+	return {}
+end
+
+local function bar(first: number, second: string): { alpha: number, beta: string, gamma: boolean, delta: { number }, epsilon: string }
+	return {}
+end
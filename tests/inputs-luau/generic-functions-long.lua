@@ -0,0 +1,13 @@
+function f<T,U>(a: T, b: U): T return a end
+local function g< T ,  U... >(a: T, ...: U...) end
+
+function someVeryLongFunctionName<TypeParameterOne, TypeParameterTwo, TypeParameterThree, TypeParameterFour, TypeParameterFive>(a: TypeParameterOne)
+	return a
+end
+
+local function someVeryLongFunctionName<TypeParameterOne, TypeParameterTwo>(argumentNumberOne: TypeParameterOne, argumentNumberTwo: TypeParameterTwo): TypeParameterOne
+	return argumentNumberOne
+end
+
+local function someVeryLongFunctionName<TypeParameterOne, TypeParameterTwo, TypeParameterThree, TypeParameterFour, Rest...>(argumentNumberOne: TypeParameterOne, ...: Rest...)
+end
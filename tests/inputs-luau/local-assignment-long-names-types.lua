@@ -0,0 +1 @@
+local someVeryLongVariableName: number, anotherVeryLongVariableName: string, yetAnotherVeryLongVariableName, finalName: boolean = 1, "foo", nil, true
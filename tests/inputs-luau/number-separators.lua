@@ -0,0 +1,8 @@
+local a = 1_000
+local b = 0x_FF
+local c = 1_000.5
+local d = 0XFF_FF
+local e = 1_000E+1_0
+local f = 0B_1010_0101
+local g = 1__0_0
+local h = .5_5
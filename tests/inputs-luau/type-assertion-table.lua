@@ -0,0 +1,10 @@
+local config = { name = "something", value = 12345, enabled = true, children = { "a", "b" }, more = 1, evenMore = 2 } :: Config
+local typed = { name = "something", value = 12345, enabled = true, children = { "a", "b" } } :: { name: string, value: number, enabled: boolean }
+local short = { name = "something" } :: Config
+local empty = {} :: Config
+
+local nested = {
+	field = { name = "something", value = 12345, enabled = true, children = { "a", "b" }, more = 1, evenMore = 2 } :: Config,
+}
+
+return { name = "something", value = 12345, enabled = true, children = { "a", "b" }, more = 1, evenMore = 2222 } :: Config
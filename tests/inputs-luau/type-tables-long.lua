@@ -0,0 +1,2 @@
+type Foo = { name: string, value: number, callback: (number) -> () } & { extra: boolean, other: string, another: number }
+type Options = { enabled: boolean, name: string, count: number, callback: (string, number) -> boolean, fallback: string? }
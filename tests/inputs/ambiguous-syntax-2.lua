@@ -0,0 +1,22 @@
+local a = b;
+(t).y = 2
+
+a = b;
+(c or d)(e)
+
+foo(bar);
+(baz)[1] = 2
+
+repeat
+	x = x + 1
+until x > 10;
+(callback)(x)
+
+local c;
+(d)()
+
+-- Semicolons which are not required are removed
+local e = f;
+g()
+do end;
+(h)()
@@ -0,0 +1,7 @@
+local result = somelongmodule.someFunction(argumentNumberOne, argumentNumberTwo, argumentNumberThree, argumentNumber456)
+local result = somelongmodule.someFunction(argumentNumberOne, argumentNumberTwo, argumentNumberThree, argumentNumber4567)
+
+result = somelongmodule.someFunction(argumentNumberOne, argumentNumberTwo, argumentNumberThree, argumentNumber456789012)
+result = somelongmodule.someFunction(argumentNumberOne, argumentNumberTwo, argumentNumberThree, argumentNumber4567890123)
+
+local someVariableName = someModule.someSubmodule.someFunction("a very long string argument that goes past the limit!!!")
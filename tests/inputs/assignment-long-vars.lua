@@ -0,0 +1,4 @@
+someVeryLongVariableName0, someVeryLongVariableName1, someVeryLongVariableName2, someVeryLongVariableName3, someVeryLongVariableName4, someVeryLongVariableName5 = 1, 2, 3, 4, 5, 6
+local someVeryLongVariableName0, someVeryLongVariableName1, someVeryLongVariableName2, someVeryLongVariableName3, someVeryLongVariableName4, someVeryLongVariableName5 = 1, 2, 3, 4, 5, 6
+obj.field0, obj.field1, obj.field2 = someFunctionCall0(argumentNumberOne), someFunctionCall1(argumentNumberOne), someFunctionCall2(argumentNumberOne), someFunctionCall3(argumentNumberOne)
+someVeryLongVariableName0, someVeryLongVariableName1, someVeryLongVariableName2, someVeryLongVariableName3, someVeryLongVariableName4, someVeryLongVariableName5 = foo(someArgument, anotherArgument, yetAnotherArgument, andOneMoreArgument, finalArgument)
@@ -0,0 +1,16 @@
+local t = { x = { y = {} } }
+
+t.x.y.z = function(a, b)
+    local c = a + b
+  if c > 10 then
+        return c
+    end
+    for i = 1, c do print(i) end
+    return nil
+end
+
+local function setup()
+    t.x.y.z = function(value) local doubled = value * 2 return doubled end
+
+    t.aaaaaaaaaaaaaaaaaaaa.bbbbbbbbbbbbbbbbbbbbbbbb.cccccccccccccccccccccccccccccccc = function(argument1, argument2) print(argument1) return argument2 end
+end
@@ -0,0 +1,8 @@
+-- A binary minus followed by a unary minus must stay separated, otherwise `--` would start a comment
+local a = x - -y
+local b = x- -y
+local c = x - - - y
+local d = x - (-y)
+local e = x - --[[comment]] y
+local f = x - --[[comment]] -y
+local g = someLongVariableNameNumberOne - -someLongVariableNameNumberTwo - -someLongVariableNameNumberThree - -numberFour - -numberFive
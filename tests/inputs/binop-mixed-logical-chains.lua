@@ -0,0 +1,11 @@
+local x = someVeryLongConditionNameNumberOne and anotherVeryLongConditionNameNumberTwo and yetAnotherVeryLongConditionNameThree or somethingElseEntirely and lastOne or finalFallbackValue
+local y = aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa or bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb and cccccccccccccccccccccccccccccccccccccccccccc and dddddddddddddddddddddddddddddddddddddd or eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee
+local z = aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa or bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb or cccccccccccccccccccccccccccccccccccccccccccc and dddddddddddddddddddddddddddddddddddddd and eeeeeeeeeeeeeeeeeeeeeeeeeeeee
+
+if someVeryLongConditionNameNumberOne and anotherVeryLongConditionNameNumberTwo or yetAnotherVeryLongConditionNameThree and somethingElseEntirely and lastOne or finalFallbackValue then
+	print("hello")
+end
+
+local function foo()
+	return someVeryLongConditionNameNumberOne or anotherVeryLongConditionNameNumberTwo and yetAnotherVeryLongConditionNameThree and not somethingElseEntirely or lastOne
+end
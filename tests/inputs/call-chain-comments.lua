@@ -0,0 +1,25 @@
+a . b . c ( )
+a : b ( )
+local x = a . b [ 1 ] . c : d ( 1 )
+a --[[ c1 ]] . b --[[ c2 ]] ( )
+a. -- c3
+b()
+a -- c
+.b()
+a: -- c
+b()
+local y = a. --[[ c ]] b
+local z = a -- c
+.b
+foo . bar = 1
+foo -- c
+. bar = 1
+x = a.b() -- c
+:c()
+foo(a. -- c
+b, 2)
+local function f()
+	obj -- c
+		.field
+		.method()
+end
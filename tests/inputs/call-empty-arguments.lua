@@ -0,0 +1,18 @@
+f( )
+f(
+)
+f(
+
+)
+a:b( )
+a:b(
+
+)
+local x = f(  ):g(
+)
+f(a
+
+)
+local fn = function(
+
+) end
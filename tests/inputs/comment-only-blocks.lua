@@ -0,0 +1,35 @@
+do
+  -- note
+end
+if x then
+-- note
+end
+if x then
+		-- a
+elseif y then
+   --[[ b ]]
+else
+-- c
+end
+for i = 1, 10 do
+        -- note
+    end
+for k, v in pairs(t) do --[[ x ]] end
+while true do
+-- note
+-- note2
+end
+repeat
+-- note
+until x
+local function f()
+    -- note
+end
+local t = function()
+-- note
+end
+do
+	do
+-- deep
+	end
+end
@@ -0,0 +1,38 @@
+for i = 1, 10 do -- loop
+	print(i)
+end
+for k, v in pairs(t) do -- generic
+	print(k)
+end
+while x do -- while
+	print(x)
+end
+do -- do
+	print(1)
+end
+if x then -- if
+	print(1)
+elseif y then -- elseif
+	print(2)
+else -- else
+	print(3)
+end
+repeat -- repeat
+	print(1)
+until x
+local function f() -- function
+	print(1)
+end
+function g(a, b) -- function
+end
+for i = 1, 10 do --[[ block ]] print(i) end
+while x do --[[ block ]] end
+if someVeryLongConditionName and anotherVeryLongConditionName or yetAnotherVeryLongConditionName and finalConditionName then -- if
+	print(1)
+end
+for _, someVeryLongVariableName in ipairs(someVeryLongFunctionCall(withSomeArguments, andMoreArguments, andEvenMoreArgs)) do -- loop
+	print(1)
+end
+for someVeryLongIndexName = someVeryLongStartExpression, someVeryLongEndExpression, someVeryLongStepValueExpression do -- numeric
+	print(1)
+end
@@ -0,0 +1,14 @@
+function f(a
+-- comment
+) end
+
+function f(
+-- comment
+) end
+
+local x = function(
+-- c
+) end
+
+function f( -- comment
+a) end
@@ -0,0 +1,20 @@
+-- This file header is separated from the code below by a blank line,
+-- which should be kept so that it does not look like it documents `foo`
+--[[
+	License text
+]]
+
+
+local foo = require(script.Parent.foo)
+-- This comment documents `bar`, and should stay attached to it
+local bar = {}
+
+-- A standalone section comment
+   
+function bar.baz()
+	-- A comment at the start of a block
+
+	return foo
+end
+
+return bar
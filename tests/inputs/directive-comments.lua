@@ -0,0 +1,14 @@
+--!strict
+--!nolint LocalShadow
+--[[@module]]
+---@class Foo
+local   Foo = {} --@type table
+
+---@param a string
+---@return string
+local function f(a) --[[@inline]] return a end
+
+local x = {
+	--[[@pure]] first = 1,
+	second = 2, --@deprecated
+}
@@ -0,0 +1,13 @@
+function SomeVeryLongModuleName.SubModuleWithLongName.AnotherNestedTableName:methodWithAVeryLongName(firstParameter, secondParameter, thirdParameter)
+	return firstParameter
+end
+function SomeVeryLongModuleName.SubModuleWithLongName.AnotherNestedTableName.YetAnotherNestedTable.AndOneMore:methodWithAVeryLongName(a)
+	return a
+end
+function Module. -- the submodule
+	SubModule:method(a, b)
+	return a + b
+end
+function --[[ exported ]] Module.SubModule.helper(a)
+	return a
+end
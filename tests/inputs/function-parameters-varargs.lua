@@ -0,0 +1,17 @@
+local function foo(a, b, ...) end
+local function bar(...) print(...) end
+
+local function baz(aaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, cccccccccccccccccccccccccccccccc, ...)
+	return ...
+end
+
+local function qux(a, -- first parameter
+	...)
+end
+
+local function quux(a,
+	... -- remaining arguments
+)
+end
+
+local callback = function(aaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, ...) end
@@ -0,0 +1,7 @@
+for someLongKeyName, someLongValueName, anotherLongLoopVariable, yetAnotherLongLoopVariable in someIteratorFunction(withSomeArgument, andAnotherArgument, andAThirdArgument), someIteratorStateValue, initialControlValue do
+	print(someLongKeyName, someLongValueName)
+end
+
+for someLongKeyName, someLongValueName, anotherLongLoopVariable, yetAnotherLongLoopVariable in pairs(someTableValueWithALongName) do
+	print(someLongKeyName, someLongValueName)
+end
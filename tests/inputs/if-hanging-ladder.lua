@@ -0,0 +1,12 @@
+local function f()
+if someVeryLongConditionNameNumberOne and anotherVeryLongConditionNameNumberTwo or yetAnotherVeryLongConditionNameThree then
+	print(1)
+elseif someVeryLongConditionNameNumberOne and anotherVeryLongConditionNameNumberTwo or yetAnotherVeryLongConditionNameThree then
+	print(2)
+elseif someVeryLongConditionNameNumberOne and anotherVeryLongConditionNameNumberTwo or yetAnotherVeryLongConditionNameThree and fooBarBaz then
+	print(3)
+elseif foo(someVeryLongConditionNameNumberOne, anotherVeryLongConditionNameNumberTwo, yetAnotherVeryLongConditionNameThree) then
+end
+if foo(someVeryLongConditionNameNumberOne, anotherVeryLongConditionNameNumberTwo, yetAnotherVeryLongConditionNameThree, x) then
+end
+end
@@ -0,0 +1,9 @@
+local value = someObject.firstProperty.secondProperty.thirdProperty.fourthProperty.fifthProperty.sixthProperty.seventh + anotherObject.a.b.c.d.e.f
+
+local result = someObject.firstProperty.secondProperty.thirdProperty.fourthProperty:method(argument).fifthProperty.sixthProperty.seventh:call(abc)
+
+print(aaaaaaaaaaaaaaaaaaaaaaaaa.bbbbbbbbbbbbbbbbbbbbbbbbbbbbb.ccccccccccccccccccccccc, ddddddddddddddddddddddddddddddddd.eeeeeeeeeeeeeeeee.ffffffffffffffffff)
+
+if someObject.firstProperty.secondProperty.thirdProperty.fourthProperty.fifthProperty and anotherObject.a.b.c.d.e.f then
+	print(true)
+end
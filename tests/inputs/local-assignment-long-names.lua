@@ -0,0 +1,2 @@
+local someVeryLongVariableName, anotherVeryLongVariableName, yetAnotherVeryLongVariableName, finalVariableNameThatIsLong = someFunctionCall(withArguments, andMoreArguments), anotherFunctionCall(withSomeArguments), thirdValue
+local someVeryLongVariableName, anotherVeryLongVariableName, yetAnotherVeryLongVariableName, finalVariableNameThatIsLong = 1, 2
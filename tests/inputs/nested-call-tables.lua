@@ -0,0 +1,2 @@
+-- Tables nested within call arguments should not take exponential time to format
+local tree = create({ name = "level1", child = create({ name = "level2", child = create({ name = "level3", child = create({ name = "level4", child = create({ name = "level5", child = create({ name = "level6", child = create({ name = "level7", child = create({ name = "level8", child = create({ name = "level9", child = create({ name = "level10", child = create({ name = "level11", child = create({ name = "level12", child = value }) }) }) }) }) }) }) }) }) }) }) })
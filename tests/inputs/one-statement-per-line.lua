@@ -0,0 +1,9 @@
+a() b()
+a(); b()
+local x = 1 local y = 2; x = y;
+do a() b() end
+if x then a(); b() end
+while true do a() break end
+local function f() a() return end
+a(); (b)()
+local z = x; (g)()
@@ -0,0 +1,20 @@
+repeat
+	local item = queue:pop()
+until item == nil or item.priority > threshold and item.deadline < os.clock() or processedCount >= maximumProcessedCount and not isPaused
+
+local function process()
+	while true do
+		repeat
+			local item = queue:pop()
+		until item == nil or item.priority > threshold and item.deadline < os.clock() or processedCount >= maximumProcessedCount
+
+		repeat
+			step()
+		until (isFinished and not hasPendingWork and not hasPendingCallbacks) or (isCancelled and cancellationReason ~= nil)
+	end
+end
+
+repeat
+	step()
+until -- stop once everything has been processed
+	isFinished and not hasPendingWork
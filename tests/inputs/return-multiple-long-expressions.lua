@@ -0,0 +1,11 @@
+local function getValues()
+	return someLongFunctionName(argumentNumberOne), anotherLongFunctionName(argumentNumberTwo), yetAnotherValue + 12345, finalValue
+end
+
+local function getNestedValues()
+	if condition then
+		return someLongFunctionName(argumentNumberOne, argumentNumberTwo, argumentNumberThree, argumentNumberFour, five), anotherLongFunctionName(argumentNumberTwo), yetAnotherValue + 12345 + anotherValueThatIsLong + yetAnotherValueThatIsEvenLonger + finalValueInTheChain
+	end
+
+	return aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa and bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb or cccccccccccccccccccccccccccccccccccc, d
+end
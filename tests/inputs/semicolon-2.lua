@@ -0,0 +1,6 @@
+local a = 1 ;
+local b = a ; (print)(b)
+local c = b --[[ note ]] ; (print)(c)
+local d = c
+; (print)(d)
+return a ;
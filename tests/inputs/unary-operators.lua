@@ -0,0 +1,16 @@
+local a = - -x
+local b = -(-x)
+local c = not not x
+local d = #  t
+local e = not(x)
+local f = - - - x
+local g = -#t
+local h = #-x
+local i = - (- someVeryLongVariableName + anotherVeryLongVariableName + yetAnotherVeryLongVariableName + yetAnotherVeryLongName)
+local j = - - (someVeryLongVariableName + anotherVeryLongVariableName + yetAnotherVeryLongVariableName + yetAnotherVeryLongName)
+local k = foo + - - (someVeryLongVariableName + anotherVeryLongVariableName + yetAnotherVeryLongVariableName + yetAnotherVeryLongName)
+local l = not not (someVeryLongVariableName and anotherVeryLongVariableName or yetAnotherVeryLongVariableName and yetAnotherVeryLongName)
+local m = not  x
+local n = not-x
+local o = not#t
+local p = -  x ^ 2
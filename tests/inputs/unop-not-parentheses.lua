@@ -0,0 +1,9 @@
+if not (a and b) then end
+if not(a and b) then end
+if (not (a or (b and not(c)))) then end
+while not (not (a)) do end
+local x = not(a)==b
+if not ( a and b ) or not(c) then end
+repeat until not(a or b)
+if not (someVeryLongConditionName and anotherVeryLongConditionName) or not (yetAnotherVeryLongConditionName or finalConditionName) then end
+if not(someVeryLongConditionName and anotherVeryLongConditionName and yetAnotherVeryLongConditionName and finalConditionName) then end
@@ -0,0 +1,78 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use stylua_lib::{
+    format_code, format_code_with_abort_signal, AbortSignal, Config, Error, OutputVerification,
+};
+
+fn format(input: &str, abort_signal: &AbortSignal) -> Result<String, Box<Error>> {
+    format_code_with_abort_signal(
+        input,
+        Config::default(),
+        None,
+        OutputVerification::None,
+        abort_signal,
+    )
+    .map_err(Box::new)
+}
+
+const CODE: &str = r###"
+local function foo()
+    local   x =  1
+    if x then print(x) end
+end
+"###;
+
+#[test]
+fn test_default_signal_never_aborts() {
+    assert_eq!(
+        format(CODE, &AbortSignal::new()).unwrap(),
+        format_code(CODE, Config::default(), None, OutputVerification::None).unwrap()
+    );
+}
+
+#[test]
+fn test_unset_flag_does_not_abort() {
+    let flag = Arc::new(AtomicBool::new(false));
+    assert!(format(CODE, &AbortSignal::new().with_flag(flag)).is_ok());
+}
+
+#[test]
+fn test_set_flag_aborts() {
+    let flag = Arc::new(AtomicBool::new(false));
+    let abort_signal = AbortSignal::new().with_flag(flag.clone());
+    assert!(!abort_signal.is_aborted());
+
+    flag.store(true, Ordering::Relaxed);
+    assert!(abort_signal.is_aborted());
+    assert!(matches!(
+        *format(CODE, &abort_signal).unwrap_err(),
+        Error::Aborted
+    ));
+}
+
+#[test]
+fn test_future_deadline_does_not_abort() {
+    let abort_signal = AbortSignal::new().with_timeout(Duration::from_secs(60 * 60));
+    assert!(format(CODE, &abort_signal).is_ok());
+}
+
+#[test]
+fn test_passed_deadline_aborts() {
+    let abort_signal = AbortSignal::new().with_deadline(Instant::now());
+    assert!(matches!(
+        *format(CODE, &abort_signal).unwrap_err(),
+        Error::Aborted
+    ));
+}
+
+#[test]
+fn test_abort_does_not_affect_next_format() {
+    let abort_signal = AbortSignal::new().with_deadline(Instant::now());
+    assert!(format(CODE, &abort_signal).is_err());
+    assert!(format(CODE, &AbortSignal::new()).is_ok());
+}
@@ -0,0 +1,89 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str, config: Config) -> String {
+    format_code(input, config, None, OutputVerification::None).unwrap()
+}
+
+const ELSE_COMMENTS: &str = r###"
+local function foo()
+    if a then
+        print(1)
+    -- before elseif
+    elseif b then
+        print(2)
+
+            -- before else
+    --[[ block comment before else ]]
+    else
+        print(3)
+    end
+end
+"###;
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_default_comments_indented_to_block() {
+    insta::assert_snapshot!(format(ELSE_COMMENTS, Config::default()), @r###"
+    local function foo()
+    	if a then
+    		print(1)
+    		-- before elseif
+    	elseif b then
+    		print(2)
+
+    		-- before else
+    		--[[ block comment before else ]]
+    	else
+    		print(3)
+    	end
+    end
+    "###);
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_align_else_leading_comments() {
+    insta::assert_snapshot!(format(ELSE_COMMENTS, Config::default().with_align_else_leading_comments(true)), @r###"
+    local function foo()
+    	if a then
+    		print(1)
+    	-- before elseif
+    	elseif b then
+    		print(2)
+
+    	-- before else
+    	--[[ block comment before else ]]
+    	else
+    		print(3)
+    	end
+    end
+    "###);
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_align_else_leading_comments_indent_else_clauses() {
+    insta::assert_snapshot!(
+        format(
+            ELSE_COMMENTS,
+            Config::default()
+                .with_align_else_leading_comments(true)
+                .with_indent_else_clauses(true)
+        ),
+        @r###"
+    local function foo()
+    	if a then
+    		print(1)
+    		-- before elseif
+    		elseif b then
+    			print(2)
+
+    		-- before else
+    		--[[ block comment before else ]]
+    		else
+    			print(3)
+    	end
+    end
+    "###
+    );
+}
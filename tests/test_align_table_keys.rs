@@ -0,0 +1,117 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_align_table_expression_keys(true),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_align_literal_keys() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local t = {
+    [1] = "one",
+    ["two"] = 2,
+    [300] = 3,
+}
+            "###
+        ),
+        @r###"
+    local t = {
+    	[1]     = "one",
+    	["two"] = 2,
+    	[300]   = 3,
+    }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_no_align_with_non_literal_keys() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local t = {
+    [a] = 1,
+    [2] = 2,
+}
+            "###
+        ),
+        @r###"
+    local t = {
+    	[a] = 1,
+    	[2] = 2,
+    }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_align_groups() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local t = {
+    [1] = "one",
+    ["two"] = 2,
+
+    [3] = "three",
+    ["four"] = 4,
+    -- a comment resets the alignment
+    [5] = "five",
+    ["sixteen"] = 16,
+    name = "a",
+    [7] = "seven",
+    ["eight"] = 8,
+}
+            "###
+        ),
+        @r###"
+    local t = {
+    	[1]     = "one",
+    	["two"] = 2,
+
+    	[3]      = "three",
+    	["four"] = 4,
+    	-- a comment resets the alignment
+    	[5]         = "five",
+    	["sixteen"] = 16,
+    	name = "a",
+    	[7]       = "seven",
+    	["eight"] = 8,
+    }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_no_align_over_column_width() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local t = {
+    [1] = "a string which is long enough that aligning it with the next key would go over the column width, too",
+    ["a long key"] = 2,
+    [3] = 3,
+}
+            "###
+        ),
+        @r###"
+    local t = {
+    	[1] = "a string which is long enough that aligning it with the next key would go over the column width, too",
+    	["a long key"] = 2,
+    	[3] = 3,
+    }
+    "###
+    );
+}
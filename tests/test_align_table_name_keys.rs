@@ -0,0 +1,106 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_align_table_name_keys(true),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_align_name_keys() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local config = {
+    width = 10,
+    height = 200,
+    x = 3,
+    backgroundColor = "red",
+}
+"###
+        ),
+        @r###"
+    local config = {
+    	width           = 10,
+    	height          = 200,
+    	x               = 3,
+    	backgroundColor = "red",
+    }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_align_groups() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local config = {
+    width = 10,
+    height = 200,
+
+    left = 1,
+    topOffset = 2,
+    -- a comment resets the alignment
+    right = 3,
+    bottomOffset = 4,
+    [1] = "one",
+    name = "a",
+    callback = function()
+        return 1
+    end,
+    after = 1,
+    afterwards = 2,
+}
+"###
+        ),
+        @r###"
+    local config = {
+    	width  = 10,
+    	height = 200,
+
+    	left      = 1,
+    	topOffset = 2,
+    	-- a comment resets the alignment
+    	right        = 3,
+    	bottomOffset = 4,
+    	[1] = "one",
+    	name = "a",
+    	callback = function()
+    		return 1
+    	end,
+    	after      = 1,
+    	afterwards = 2,
+    }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_no_align_over_column_width() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local config = {
+    a = "a string which is long enough that aligning it with the next key would go over the column width",
+    aVeryLongKeyName = 1,
+}
+local singleLine = { width = 10, height = 200 }
+"###
+        ),
+        @r###"
+    local config = {
+    	a = "a string which is long enough that aligning it with the next key would go over the column width",
+    	aVeryLongKeyName = 1,
+    }
+    local singleLine = { width = 10, height = 200 }
+    "###
+    );
+}
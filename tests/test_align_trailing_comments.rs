@@ -0,0 +1,115 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_align_trailing_comments(true),
+        None,
+        OutputVerification::Full,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_align_trailing_comments() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local a = 1 -- one
+local longer_name = 2 -- two
+call() -- three
+            "###
+        ),
+        @r###"
+    local a = 1           -- one
+    local longer_name = 2 -- two
+    call()                -- three
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_align_trailing_comments_groups() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local x = 1 -- first group
+local yy = 2 -- first group
+
+local foo = 1 -- second group
+local a = 2 -- second group
+-- a comment
+local bar = 3 -- third group
+local b = 4; -- third group
+print(b)
+local c = 5 -- alone
+do
+    local inner = 1 -- nested
+    local i = 2 -- nested
+end
+            "###
+        ),
+        @r###"
+    local x = 1  -- first group
+    local yy = 2 -- first group
+
+    local foo = 1 -- second group
+    local a = 2   -- second group
+    -- a comment
+    local bar = 3 -- third group
+    local b = 4   -- third group
+    print(b)
+    local c = 5 -- alone
+    do
+    	local inner = 1 -- nested
+    	local i = 2     -- nested
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_align_trailing_comments_over_width() {
+    insta::assert_snapshot!(
+        format_code(
+            r###"
+local a = 1 -- short
+local something_long = 2 -- a longer comment
+            "###,
+            Config::default()
+                .with_align_trailing_comments(true)
+                .with_column_width(40),
+            None,
+            OutputVerification::Full,
+        )
+        .unwrap(),
+        @r###"
+    local a = 1 -- short
+    local something_long = 2 -- a longer comment
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_multiline_statement_not_aligned() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local t = { -- table
+    1,
+} -- end of table
+local s = 5 -- after
+            "###
+        ),
+        @r###"
+    local t = { -- table
+    	1,
+    } -- end of table
+    local s = 5 -- after
+    "###
+    );
+}
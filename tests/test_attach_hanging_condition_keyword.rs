@@ -0,0 +1,99 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str, attach_hanging_condition_keyword: bool) -> String {
+    format_code(
+        input,
+        Config::default().with_attach_hanging_condition_keyword(attach_hanging_condition_keyword),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+const INPUT: &str = r###"
+if someVeryLongConditionName and anotherVeryLongConditionName or yetAnotherVeryLongConditionName and finalConditionName then
+    print("hi")
+elseif someVeryLongConditionName and anotherVeryLongConditionName or yetAnotherVeryLongConditionName and finalConditionName then
+    print("hi")
+end
+
+while someVeryLongConditionName and anotherVeryLongConditionName or yetAnotherVeryLongConditionName and finalConditionName do
+    print("hi")
+end
+"###;
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_keyword_on_new_line() {
+    insta::assert_snapshot!(format(INPUT, false), @r###"
+    if
+    	someVeryLongConditionName and anotherVeryLongConditionName
+    	or yetAnotherVeryLongConditionName and finalConditionName
+    then
+    	print("hi")
+    elseif
+    	someVeryLongConditionName and anotherVeryLongConditionName
+    	or yetAnotherVeryLongConditionName and finalConditionName
+    then
+    	print("hi")
+    end
+
+    while
+    	someVeryLongConditionName and anotherVeryLongConditionName
+    	or yetAnotherVeryLongConditionName and finalConditionName
+    do
+    	print("hi")
+    end
+    "###);
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_attach_keyword() {
+    insta::assert_snapshot!(format(INPUT, true), @r###"
+    if
+    	someVeryLongConditionName and anotherVeryLongConditionName
+    	or yetAnotherVeryLongConditionName and finalConditionName then
+    	print("hi")
+    elseif
+    	someVeryLongConditionName and anotherVeryLongConditionName
+    	or yetAnotherVeryLongConditionName and finalConditionName then
+    	print("hi")
+    end
+
+    while
+    	someVeryLongConditionName and anotherVeryLongConditionName
+    	or yetAnotherVeryLongConditionName and finalConditionName do
+    	print("hi")
+    end
+    "###);
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_attach_keyword_idempotent() {
+    let formatted = format(INPUT, true);
+    assert_eq!(format(&formatted, true), formatted);
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_attach_keyword_comments() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+if someVeryLongConditionName -- comment
+    and anotherVeryLongConditionName then
+end
+"###,
+            true
+        ),
+        @r###"
+    if
+    	someVeryLongConditionName -- comment
+    	and anotherVeryLongConditionName
+    then
+    end
+    "###
+    );
+}
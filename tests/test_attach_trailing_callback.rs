@@ -0,0 +1,106 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_attach_trailing_callback(true),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_single_argument_before_callback() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+connectToTheSignalWithAVeryLongName(theSignalWhichIsBeingConnectedToWithAnEvenLongerNameThanBefore, function(player, character)
+    print(player, character)
+end)
+"###
+        ),
+        @r###"
+    connectToTheSignalWithAVeryLongName(theSignalWhichIsBeingConnectedToWithAnEvenLongerNameThanBefore, function(player, character)
+    	print(player, character)
+    end)
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_multiple_arguments_before_callback() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+someVeryLongFunctionName(argumentNumberOne, argumentNumberTwo, argumentNumberThree, anotherArgument, function(a, b)
+    print(a)
+end)
+someVeryLongFunctionName(argumentNumberOne, argumentNumberTwo, argumentNumberThree, anotherArgument, andAnotherOne, function(a, b)
+    print(a)
+end)
+"###
+        ),
+        @r###"
+    someVeryLongFunctionName(argumentNumberOne, argumentNumberTwo, argumentNumberThree, anotherArgument, function(a, b)
+    	print(a)
+    end)
+    someVeryLongFunctionName(argumentNumberOne, argumentNumberTwo, argumentNumberThree, anotherArgument, andAnotherOne, function(a, b)
+    	print(a)
+    end)
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_callback_not_last() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+someVeryLongFunctionName(argumentNumberOne, argumentNumberTwo, argumentNumberThree, anotherArgument, andAnotherOne, function(a, b)
+    print(a)
+end, anotherArgument)
+"###
+        ),
+        @r###"
+    someVeryLongFunctionName(
+    	argumentNumberOne,
+    	argumentNumberTwo,
+    	argumentNumberThree,
+    	anotherArgument,
+    	andAnotherOne,
+    	function(a, b)
+    		print(a)
+    	end,
+    	anotherArgument
+    )
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_callback_with_comments() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+someVeryLongFunctionName(argumentNumberOne, -- comment
+    argumentNumberTwo, function(a, b)
+    print(a)
+end)
+"###
+        ),
+        @r###"
+    someVeryLongFunctionName(
+    	argumentNumberOne, -- comment
+    	argumentNumberTwo,
+    	function(a, b)
+    		print(a)
+    	end
+    )
+    "###
+    );
+}
@@ -0,0 +1,103 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_blank_line_after_requires(true),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_without_separator() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+-- Module header
+local Players = require(script.Parent.Players)
+local Util = require("util").helpers -- helpers
+local Module = {}
+return Module
+"###
+        ),
+        @r###"
+    -- Module header
+    local Players = require(script.Parent.Players)
+    local Util = require("util").helpers -- helpers
+
+    local Module = {}
+    return Module
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_with_separator() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local Players = require(script.Parent.Players)
+local Util = require(script.Parent.Util)
+
+
+
+local Module = {}
+return Module
+"###
+        ),
+        @r###"
+    local Players = require(script.Parent.Players)
+    local Util = require(script.Parent.Util)
+
+    local Module = {}
+    return Module
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_comment_after_requires() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local Players = require(script.Parent.Players)
+-- Returns the module
+return Players
+"###
+        ),
+        @r###"
+    local Players = require(script.Parent.Players)
+
+    -- Returns the module
+    return Players
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_nested_requires() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local function load()
+    local Players = require(script.Parent.Players)
+    return Players
+end
+local Util = require(script.Parent.Util)
+"###
+        ),
+        @r###"
+    local function load()
+    	local Players = require(script.Parent.Players)
+    	return Players
+    end
+    local Util = require(script.Parent.Util)
+    "###
+    );
+}
@@ -0,0 +1,89 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_collapse_simple_statement(true),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_collapse_do_block() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+do
+    return x
+end
+do y = x
+end
+            "###
+        ),
+        @r###"
+    do return x end
+    do y = x end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_do_block_with_two_statements_does_not_collapse() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+do local a = 1 local b = 2 end
+            "###
+        ),
+        @r###"
+    do
+    	local a = 1
+    	local b = 2
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_do_block_with_local_does_not_collapse() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+do local x = 1 end
+do local function f() end end
+            "###
+        ),
+        @r###"
+    do
+    	local x = 1
+    end
+    do
+    	local function f() end
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_do_block_with_comments_does_not_collapse() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+do -- comment
+    return x
+end
+            "###
+        ),
+        @r###"
+    do -- comment
+    	return x
+    end
+    "###
+    );
+}
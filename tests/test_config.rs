@@ -0,0 +1,36 @@
+use stylua_lib::{Config, IndentType, QuoteStyle};
+
+#[test]
+fn test_config_getters() {
+    let config = Config::default()
+        .with_column_width(80)
+        .with_indent_type(IndentType::Spaces)
+        .with_quote_style(QuoteStyle::ForceSingle)
+        .with_max_inline_method_chain(3)
+        .with_merge_string_concatenation(true);
+
+    assert_eq!(config.column_width(), 80);
+    assert!(matches!(config.indent_type(), IndentType::Spaces));
+    assert!(matches!(config.quote_style(), QuoteStyle::ForceSingle));
+    assert_eq!(config.max_inline_method_chain(), 3);
+    assert!(config.merge_string_concatenation());
+
+    // Untouched options keep their defaults
+    assert_eq!(config.indent_width(), 4);
+    assert!(!config.no_call_parentheses());
+}
+
+#[test]
+fn test_config_default_constants() {
+    let config = Config::default();
+
+    assert_eq!(config.column_width(), Config::DEFAULT_COLUMN_WIDTH);
+    assert_eq!(config.indent_width(), Config::DEFAULT_INDENT_WIDTH);
+    assert!(matches!(Config::DEFAULT_INDENT_TYPE, IndentType::Tabs));
+    assert!(matches!(config.indent_type(), IndentType::Tabs));
+    assert!(matches!(
+        Config::DEFAULT_QUOTE_STYLE,
+        QuoteStyle::AutoPreferDouble
+    ));
+    assert!(matches!(config.quote_style(), QuoteStyle::AutoPreferDouble));
+}
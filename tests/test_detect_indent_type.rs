@@ -0,0 +1,31 @@
+use stylua_lib::{detect_indent_type, IndentType};
+
+#[test]
+fn test_tabs() {
+    assert_eq!(
+        detect_indent_type("if x then\n\tprint(1)\n\tprint(2)\nend\n"),
+        Some(IndentType::Tabs)
+    );
+}
+
+#[test]
+fn test_spaces() {
+    assert_eq!(
+        detect_indent_type("if x then\n    print(1)\n    print(2)\nend\n"),
+        Some(IndentType::Spaces)
+    );
+}
+
+#[test]
+fn test_majority() {
+    assert_eq!(
+        detect_indent_type("if x then\n  print(1)\n\tprint(2)\n\tprint(3)\nend\n"),
+        Some(IndentType::Tabs)
+    );
+}
+
+#[test]
+fn test_undetermined() {
+    assert_eq!(detect_indent_type("local x = 1\n\n    \nprint(x)\n"), None);
+    assert_eq!(detect_indent_type("do\n\tprint(1)\n  print(2)\nend\n"), None);
+}
@@ -0,0 +1,48 @@
+use stylua_lib::{format_code_with_filename, Config, Error, OutputVerification};
+
+#[test]
+fn test_filename_does_not_affect_formatting() {
+    let with_filename = format_code_with_filename(
+        "local   x=1",
+        Some("foo.lua"),
+        Config::default(),
+        None,
+        OutputVerification::None,
+    );
+    let without_filename = format_code_with_filename(
+        "local   x=1",
+        None,
+        Config::default(),
+        None,
+        OutputVerification::None,
+    );
+
+    assert_eq!(with_filename.unwrap(), without_filename.unwrap());
+}
+
+#[test]
+fn test_filename_in_error() {
+    let with_filename = format_code_with_filename(
+        "local = 1",
+        Some("foo.lua"),
+        Config::default(),
+        None,
+        OutputVerification::None,
+    );
+    match with_filename {
+        Err(Error::FileError(filename, error)) => {
+            assert_eq!(filename, "foo.lua");
+            assert!(matches!(*error, Error::ParseError(_)));
+        }
+        other => panic!("expected file error, got {:?}", other),
+    }
+
+    let without_filename = format_code_with_filename(
+        "local = 1",
+        None,
+        Config::default(),
+        None,
+        OutputVerification::None,
+    );
+    assert!(matches!(without_filename, Err(Error::ParseError(_))));
+}
@@ -0,0 +1,40 @@
+use stylua_lib::{format_block_code, Config, Error};
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_format_block() {
+    insta::assert_snapshot!(
+        format_block_code(
+            r###"
+local   x   =   1
+if x then print( 'hello' ) end
+"###,
+            Config::default()
+        )
+        .unwrap(),
+        @r###"
+    local x = 1
+    if x then
+    	print("hello")
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_expression_is_error() {
+    assert!(matches!(
+        format_block_code("1 + foo(bar)", Config::default()),
+        Err(Error::ExpressionNotBlock)
+    ));
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_invalid_code_is_parse_error() {
+    assert!(matches!(
+        format_block_code("local = 1", Config::default()),
+        Err(Error::ParseError(_))
+    ));
+}
@@ -0,0 +1,39 @@
+use stylua_lib::{format_code_if_changed, Config, LineEndings};
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_unchanged() {
+    assert_eq!(
+        format_code_if_changed("local   x =  1", "local x = 1\n", Config::default()).unwrap(),
+        None
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_changed() {
+    assert_eq!(
+        format_code_if_changed("local   x =  2", "local x = 1\n", Config::default()).unwrap(),
+        Some("local x = 2\n".to_owned())
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_changed_line_endings() {
+    assert_eq!(
+        format_code_if_changed(
+            "local   x =  1",
+            "local x = 1\n",
+            Config::default().with_line_endings(LineEndings::Windows)
+        )
+        .unwrap(),
+        Some("local x = 1\r\n".to_owned())
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_parse_error() {
+    assert!(format_code_if_changed("local = 1", "", Config::default()).is_err());
+}
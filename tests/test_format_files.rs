@@ -0,0 +1,70 @@
+use std::fs;
+use std::path::PathBuf;
+use stylua_lib::{format_files, Config, Error, OutputVerification};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("stylua-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_format_files() {
+    let dir = temp_dir("format-files");
+    let unformatted = dir.join("unformatted.lua");
+    let formatted = dir.join("formatted.lua");
+    let invalid = dir.join("invalid.lua");
+    let missing = dir.join("missing.lua");
+
+    fs::write(&unformatted, "local   x =   1").unwrap();
+    fs::write(&formatted, "local x = 1\n").unwrap();
+    fs::write(&invalid, "local = 1").unwrap();
+
+    let paths = [&missing, &unformatted, &invalid, &formatted];
+    let results = format_files(&paths, Config::default(), OutputVerification::Full);
+
+    let result_paths: Vec<_> = results.iter().map(|(path, _)| path).collect();
+    assert_eq!(result_paths, paths);
+
+    assert!(matches!(
+        &results[0].1,
+        Err(Error::FileError(_, error)) if matches!(**error, Error::IoError(_))
+    ));
+    assert!(matches!(results[1].1, Ok(true)));
+    assert!(matches!(
+        &results[2].1,
+        Err(Error::FileError(_, error)) if matches!(**error, Error::ParseError(_))
+    ));
+    assert!(matches!(results[3].1, Ok(false)));
+
+    assert_eq!(fs::read_to_string(&unformatted).unwrap(), "local x = 1\n");
+    assert_eq!(fs::read_to_string(&invalid).unwrap(), "local = 1");
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_format_more_files_than_cores() {
+    let dir = temp_dir("format-many-files");
+    let paths: Vec<_> = (0..num_cpus::get() * 4 + 1)
+        .map(|index| {
+            let path = dir.join(format!("file{}.lua", index));
+            fs::write(&path, format!("local   x =   {}", index)).unwrap();
+            path
+        })
+        .collect();
+
+    let results = format_files(&paths, Config::default(), OutputVerification::Full);
+
+    assert_eq!(results.len(), paths.len());
+    for (index, (path, result)) in results.iter().enumerate() {
+        assert_eq!(path, &paths[index]);
+        assert!(matches!(result, Ok(true)));
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            format!("local x = {}\n", index)
+        );
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
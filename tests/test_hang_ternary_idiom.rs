@@ -0,0 +1,101 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_hang_ternary_idiom(true),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_ternary_fits() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local value = someVeryLongConditionName == anotherVeryLongValueName and firstResultingValue or secondResultingValue
+"###
+        ),
+        @r###"
+    local value = someVeryLongConditionName == anotherVeryLongValueName and firstResultingValue or secondResultingValue
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_ternary_hangs_as_unit() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local value = someVeryLongConditionName == anotherVeryLongValueName and computeTheFirstResult(argumentOne, argumentTwo) or computeTheSecondResult(argumentOne)
+local function getValue()
+    return someVeryLongConditionName == anotherVeryLongValueName and computeTheFirstResult(argumentOne, argumentTwo) or computeTheSecondResult(argumentOne)
+end
+"###
+        ),
+        @r###"
+    local value = someVeryLongConditionName == anotherVeryLongValueName
+    	and computeTheFirstResult(argumentOne, argumentTwo)
+    	or computeTheSecondResult(argumentOne)
+    local function getValue()
+    	return someVeryLongConditionName == anotherVeryLongValueName
+    		and computeTheFirstResult(argumentOne, argumentTwo)
+    		or computeTheSecondResult(argumentOne)
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_nested_ternary() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local value = isEnabled and (isAdministrator and administratorDefaultValue or userDefaultValue) or disabledFallbackValueWithAName
+local value = isEnabled and (isAdministratorWithAVeryVeryLongName and administratorDefaultValueWithLongName or userDefaultValueWithAnotherLongName) or disabledFallbackValue
+local value = isEnabled and firstValue or (isAdministratorWithAVeryVeryLongName and administratorDefaultValueWithLongName or userDefaultValueWithAnotherLongName)
+"###
+        ),
+        @r###"
+    local value = isEnabled
+    	and (isAdministrator and administratorDefaultValue or userDefaultValue)
+    	or disabledFallbackValueWithAName
+    local value = isEnabled
+    	and (
+    		isAdministratorWithAVeryVeryLongName
+    		and administratorDefaultValueWithLongName
+    		or userDefaultValueWithAnotherLongName
+    	)
+    	or disabledFallbackValue
+    local value = isEnabled
+    	and firstValue
+    	or (
+    		isAdministratorWithAVeryVeryLongName
+    		and administratorDefaultValueWithLongName
+    		or userDefaultValueWithAnotherLongName
+    	)
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_ternary_chain() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local value = isFirstConditionTrueWithLongName and firstResultValueWithLongName or isSecondConditionTrue and secondResultValue or fallbackResultValue
+"###
+        ),
+        @r###"
+    local value = isFirstConditionTrueWithLongName and firstResultValueWithLongName
+    	or isSecondConditionTrue and secondResultValue
+    	or fallbackResultValue
+    "###
+    );
+}
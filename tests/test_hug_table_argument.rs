@@ -0,0 +1,108 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str, config: Config) -> String {
+    format_code(input, config, None, OutputVerification::None).unwrap()
+}
+
+const INPUT: &str = r###"
+foo({ aaaaaaaaaaaaaaaaaaaaaaaaaaa = 1, bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb = 2, cccccccccccccccccccccccccccccccccc = 3 })
+local x = obj:method({
+	a = 1,
+})
+foo({
+	a = 1,
+}).bar = 1
+foo({ a = 1 })
+"###;
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_hug_table_argument() {
+    insta::assert_snapshot!(format(INPUT, Config::default()), @r###"
+    foo({
+    	aaaaaaaaaaaaaaaaaaaaaaaaaaa = 1,
+    	bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb = 2,
+    	cccccccccccccccccccccccccccccccccc = 3,
+    })
+    local x = obj:method({
+    	a = 1,
+    })
+    foo({
+    	a = 1,
+    }).bar = 1
+    foo({ a = 1 })
+    "###);
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_no_hug_table_argument() {
+    insta::assert_snapshot!(format(INPUT, Config::default().with_hug_table_argument(false)), @r###"
+    foo(
+    	{
+    		aaaaaaaaaaaaaaaaaaaaaaaaaaa = 1,
+    		bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb = 2,
+    		cccccccccccccccccccccccccccccccccc = 3,
+    	}
+    )
+    local x = obj:method(
+    	{
+    		a = 1,
+    	}
+    )
+    foo(
+    	{
+    		a = 1,
+    	}
+    ).bar = 1
+    foo({ a = 1 })
+    "###);
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_no_call_parentheses_hug_table_argument() {
+    insta::assert_snapshot!(format(INPUT, Config::default().with_no_call_parentheses(true)), @r###"
+    foo {
+    	aaaaaaaaaaaaaaaaaaaaaaaaaaa = 1,
+    	bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb = 2,
+    	cccccccccccccccccccccccccccccccccc = 3,
+    }
+    local x = obj:method {
+    	a = 1,
+    }
+    foo({
+    	a = 1,
+    }).bar = 1
+    foo { a = 1 }
+    "###);
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_no_call_parentheses_no_hug_table_argument() {
+    insta::assert_snapshot!(
+        format(
+            INPUT,
+            Config::default()
+                .with_no_call_parentheses(true)
+                .with_hug_table_argument(false)
+        ),
+        @r###"
+    foo {
+    	aaaaaaaaaaaaaaaaaaaaaaaaaaa = 1,
+    	bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb = 2,
+    	cccccccccccccccccccccccccccccccccc = 3,
+    }
+    local x = obj:method {
+    	a = 1,
+    }
+    foo(
+    	{
+    		a = 1,
+    	}
+    ).bar = 1
+    foo { a = 1 }
+    "###
+    );
+}
@@ -0,0 +1,98 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_indent_else_clauses(true),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_indent_else_clauses() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+if a then
+    b()
+    -- comment
+elseif c then
+    d()
+else
+    e()
+end
+            "###
+        ),
+        @r###"
+    if a then
+    	b()
+    	-- comment
+    	elseif c then
+    		d()
+    	else
+    		e()
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_indent_else_clauses_nested() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local function foo()
+    if x then return 1 else return 2 end
+end
+            "###
+        ),
+        @r###"
+    local function foo()
+    	if x then
+    		return 1
+    		else
+    			return 2
+    	end
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_indent_else_clauses_hanging_conditions() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+if someVeryLongConditionNameNumberOne and anotherVeryLongConditionNameNumberTwo or yetAnotherVeryLongConditionNameThree then
+    print(1)
+elseif someVeryLongConditionNameNumberOne and anotherVeryLongConditionNameNumberTwo or yetAnotherVeryLongConditionNameThree then
+    print(2)
+elseif someVeryLongConditionNameNumberOne and anotherVeryLongConditionNameNumberTwo or yetAnotherVeryLongConditionNameThree and fooBarBaz then
+    print(3)
+end
+"###
+        ),
+        @r###"
+    if
+    	someVeryLongConditionNameNumberOne and anotherVeryLongConditionNameNumberTwo or yetAnotherVeryLongConditionNameThree
+    then
+    	print(1)
+    	elseif
+    		someVeryLongConditionNameNumberOne and anotherVeryLongConditionNameNumberTwo
+    		or yetAnotherVeryLongConditionNameThree
+    	then
+    		print(2)
+    	elseif
+    		someVeryLongConditionNameNumberOne and anotherVeryLongConditionNameNumberTwo
+    		or yetAnotherVeryLongConditionNameThree and fooBarBaz
+    	then
+    		print(3)
+    end
+    "###
+    );
+}
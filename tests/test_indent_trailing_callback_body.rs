@@ -0,0 +1,72 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str, indent_trailing_callback_body: bool) -> String {
+    format_code(
+        input,
+        Config::default()
+            .with_attach_trailing_callback(true)
+            .with_indent_trailing_callback_body(indent_trailing_callback_body),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+const CALLBACKS: &str = r###"
+Players.PlayerAdded:Connect(function(player)
+    local character = player.Character
+    print(player.Name, "joined")
+    setup(character)
+end)
+
+local function init()
+    task.spawn(loadAssets, assetList, function(loaded, total)
+        if loaded == total then
+            print("done")
+        end
+        update(loaded)
+    end)
+end
+"###;
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_block_indent_callback_body() {
+    insta::assert_snapshot!(format(CALLBACKS, false), @r###"
+    Players.PlayerAdded:Connect(function(player)
+    	local character = player.Character
+    	print(player.Name, "joined")
+    	setup(character)
+    end)
+
+    local function init()
+    	task.spawn(loadAssets, assetList, function(loaded, total)
+    		if loaded == total then
+    			print("done")
+    		end
+    		update(loaded)
+    	end)
+    end
+    "###);
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_extra_indent_callback_body() {
+    insta::assert_snapshot!(format(CALLBACKS, true), @r###"
+    Players.PlayerAdded:Connect(function(player)
+    		local character = player.Character
+    		print(player.Name, "joined")
+    		setup(character)
+    	end)
+
+    local function init()
+    	task.spawn(loadAssets, assetList, function(loaded, total)
+    			if loaded == total then
+    				print("done")
+    			end
+    			update(loaded)
+    		end)
+    end
+    "###);
+}
@@ -0,0 +1,92 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_inline_condition_trailing_comments(true),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_trailing_comments() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+if x -- comment
+then
+    print(1)
+elseif y --[[ comment ]] then
+    print(2)
+end
+
+while a and b -- comment
+do
+end
+"###
+        ),
+        @r###"
+    if x then -- comment
+    	print(1)
+    elseif y then --[[ comment ]]
+    	print(2)
+    end
+
+    while a and b do -- comment
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_interior_comments() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+if a --[[ comment ]] and b then
+end
+
+while a -- comment
+    and b do
+end
+"###
+        ),
+        @r###"
+    if
+    	a --[[ comment ]]
+    	and b
+    then
+    end
+
+    while
+    	a -- comment
+    	and b
+    do
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_keyword_comments() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+if x -- comment
+then -- another comment
+end
+"###
+        ),
+        @r###"
+    if
+    	x -- comment
+    then -- another comment
+    end
+    "###
+    );
+}
@@ -0,0 +1,179 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(input, Config::default(), None, OutputVerification::None).unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_expand_directive() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+-- stylua: expand
+local t = { a = 1, b = { 1, 2 }, c = {} }
+-- stylua: expand
+foo(a, b)
+-- stylua: expand
+foo({ a = 1 })
+local u = { a = 1 }
+            "###
+        ),
+        @r###"
+    -- stylua: expand
+    local t = {
+    	a = 1,
+    	b = {
+    		1,
+    		2,
+    	},
+    	c = {},
+    }
+    -- stylua: expand
+    foo(
+    	a,
+    	b
+    )
+    -- stylua: expand
+    foo({
+    	a = 1,
+    })
+    local u = { a = 1 }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_collapse_directive() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+-- stylua: collapse
+local v = {
+	a = 1,
+	b = {
+		1,
+		2,
+	},
+}
+-- stylua: collapse
+foo({
+	a = 1,
+}, {
+	b = 2,
+})
+local x = {
+	a = 1,
+}
+            "###
+        ),
+        @r###"
+    -- stylua: collapse
+    local v = { a = 1, b = { 1, 2 } }
+    -- stylua: collapse
+    foo({ a = 1 }, { b = 2 })
+    local x = {
+    	a = 1,
+    }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_collapse_directive_comments() {
+    // Comments within a table always keep it expanded, as collapsing it would change the code
+    insta::assert_snapshot!(
+        format(
+            r###"
+-- stylua: collapse
+local w = {
+	a = 1, -- comment
+}
+            "###
+        ),
+        @r###"
+    -- stylua: collapse
+    local w = {
+    	a = 1, -- comment
+    }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_collapse_directive_over_width() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+-- stylua: collapse
+local t = {
+	aaaaaaaaaaaaaaaaaaaaaaaaaaaaaa = 1, bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb = 2, cccccccccccccccccccccccccccccccc = 3,
+}
+            "###
+        ),
+        @r###"
+    -- stylua: collapse
+    local t = {
+    	aaaaaaaaaaaaaaaaaaaaaaaaaaaaaa = 1,
+    	bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb = 2,
+    	cccccccccccccccccccccccccccccccc = 3,
+    }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_directive_does_not_apply_to_nested_blocks() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+-- stylua: collapse
+if x then
+	local y = {
+		a = 1,
+	}
+end
+            "###
+        ),
+        @r###"
+    -- stylua: collapse
+    if x then
+    	local y = {
+    		a = 1,
+    	}
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_collapse_directive_max_inline_width() {
+    insta::assert_snapshot!(
+        format_code(
+            r###"
+foo(aaaa, bbbb, cccc, ddd)
+-- stylua: collapse
+foo(aaaa, bbbb, cccc, ddd)
+            "###,
+            Config::default().with_max_inline_width(20),
+            None,
+            OutputVerification::None
+        )
+        .unwrap(),
+        @r###"
+    foo(
+    	aaaa,
+    	bbbb,
+    	cccc,
+    	ddd
+    )
+    -- stylua: collapse
+    foo(aaaa, bbbb, cccc, ddd)
+    "###
+    );
+}
@@ -0,0 +1,44 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str, config: Config) -> String {
+    format_code(input, config, None, OutputVerification::Full).unwrap()
+}
+
+// Long bracket strings and comments whose interior lines have meaningful trailing whitespace
+const LONG_STRINGS: &str = "local s = [[line one   \n  line two\t\n   \n]]\nlocal t = {\n\tkey = [==[\n  a  \n  ]==],\n}\nprint([[x  \ny  ]]) --[[ comment  \n  more  \n]]\n";
+
+#[test]
+fn test_long_strings_byte_identical() {
+    assert_eq!(format(LONG_STRINGS, Config::default()), LONG_STRINGS);
+}
+
+#[test]
+fn test_long_strings_byte_identical_whitespace_only() {
+    assert_eq!(
+        format(LONG_STRINGS, Config::default().with_whitespace_only(true)),
+        LONG_STRINGS
+    );
+}
+
+#[test]
+fn test_long_strings_byte_identical_align_trailing_comments() {
+    assert_eq!(
+        format(
+            LONG_STRINGS,
+            Config::default().with_align_trailing_comments(true)
+        ),
+        LONG_STRINGS
+    );
+}
+
+#[test]
+fn test_long_strings_keep_trailing_whitespace_when_reindented() {
+    let input = "local function f()\n\tlocal s = [[\n    a  \n      b\t\n    ]]\nend\n";
+    assert_eq!(
+        format(
+            input,
+            Config::default().with_reindent_multiline_strings(true)
+        ),
+        "local function f()\n\tlocal s = [[\n\ta  \n\t  b\t\n\t]]\nend\n"
+    );
+}
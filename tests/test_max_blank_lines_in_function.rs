@@ -0,0 +1,80 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str, max_blank_lines_in_function: usize) -> String {
+    format_code(
+        input,
+        Config::default().with_max_blank_lines_in_function(max_blank_lines_in_function),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+const BLANK_LINES: &str = r###"
+local Module = {}
+
+
+
+function Module.run()
+    local x = 1
+
+
+
+    local callback = function()
+        print(x)
+
+
+        return x
+    end
+
+
+    return callback
+end
+
+
+return Module
+"###;
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_no_blank_lines_in_function() {
+    insta::assert_snapshot!(format(BLANK_LINES, 0), @r###"
+    local Module = {}
+
+    function Module.run()
+    	local x = 1
+    	local callback = function()
+    		print(x)
+    		return x
+    	end
+    	return callback
+    end
+
+    return Module
+    "###);
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_two_blank_lines_in_function() {
+    insta::assert_snapshot!(format(BLANK_LINES, 2), @r###"
+    local Module = {}
+
+    function Module.run()
+    	local x = 1
+
+
+    	local callback = function()
+    		print(x)
+
+
+    		return x
+    	end
+
+
+    	return callback
+    end
+
+    return Module
+    "###);
+}
@@ -0,0 +1,81 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_with_limit(input, 3)
+}
+
+fn format_with_limit(input: &str, max_inline_method_chain: usize) -> String {
+    format_code(
+        input,
+        Config::default().with_max_inline_method_chain(max_inline_method_chain),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_chain_at_limit() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+builder.options:setName("foo"):build()
+            "###
+        ),
+        @r###"
+    builder.options:setName("foo"):build()
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_chain_over_limit() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+builder.options:setName("foo"):setValue(1):build()
+            "###
+        ),
+        @r###"
+    builder
+    	.options
+    	:setName("foo")
+    	:setValue(1)
+    	:build()
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_index_and_call_chain_over_limit() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local x = a.b.c().d().e()
+            "###
+        ),
+        @r###"
+    local x = a
+    	.b
+    	.c()
+    	.d()
+    	.e()
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_single_method_call() {
+    let input = "builder:build()\n";
+    insta::assert_snapshot!(format_with_limit(input, 1), @r###"
+    builder:build()
+    "###);
+    insta::assert_snapshot!(format_with_limit(input, 0), @r###"
+    builder
+    	:build()
+    "###);
+}
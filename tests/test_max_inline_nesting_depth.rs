@@ -0,0 +1,102 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_max_inline_nesting_depth(2),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_table_at_limit() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local a = { a = { b = 1 } }
+local b = { a = { b = { c = 1 } } }
+            "###
+        ),
+        @r###"
+    local a = { a = { b = 1 } }
+    local b = {
+    	a = { b = { c = 1 } },
+    }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_call_at_limit() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+foo(bar(1))
+foo(bar(baz(1)))
+foo(x, { a = 1 })
+foo(x, { a = { b = 1 } })
+            "###
+        ),
+        @r###"
+    foo(bar(1))
+    foo(
+    	bar(baz(1))
+    )
+    foo(x, { a = 1 })
+    foo(
+    	x,
+    	{ a = { b = 1 } }
+    )
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_table_argument_at_limit() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+foo({ a = { b = 1 } })
+foo({ a = { b = { c = 1 } } })
+            "###
+        ),
+        @r###"
+    foo({ a = { b = 1 } })
+    foo({
+    	a = { b = { c = 1 } },
+    })
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_collapse_directive() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+-- stylua: collapse
+local a = { a = { b = { c = 1 } } }
+            "###
+        ),
+        @r###"
+    -- stylua: collapse
+    local a = { a = { b = { c = 1 } } }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_unlimited_by_default() {
+    let code = "foo(bar(baz({ a = { b = { c = 1 } } })))\n";
+    assert_eq!(
+        format_code(code, Config::default(), None, OutputVerification::None).unwrap(),
+        code
+    );
+}
@@ -0,0 +1,93 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_max_inline_width(20),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_call_at_limit() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+foo(aaaa, bbbb, cccc, dd)
+foo(aaaa, bbbb, cccc, ddd)
+            "###
+        ),
+        @r###"
+    foo(aaaa, bbbb, cccc, dd)
+    foo(
+    	aaaa,
+    	bbbb,
+    	cccc,
+    	ddd
+    )
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_table_at_limit() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local a = { 1, 2, 3, 4, 5, 6, 77 }
+local b = { 1, 2, 3, 4, 5, 6, 777 }
+            "###
+        ),
+        @r###"
+    local a = { 1, 2, 3, 4, 5, 6, 77 }
+    local b = {
+    	1,
+    	2,
+    	3,
+    	4,
+    	5,
+    	6,
+    	777,
+    }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_table_argument_at_limit() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+foo({ 1, 2, 3, 4, 5, 6, 77 })
+foo({ 1, 2, 3, 4, 5, 6, 777 })
+            "###
+        ),
+        @r###"
+    foo({ 1, 2, 3, 4, 5, 6, 77 })
+    foo({
+    	1,
+    	2,
+    	3,
+    	4,
+    	5,
+    	6,
+    	777,
+    })
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_unlimited_by_default() {
+    let code = "foo(aaaa, bbbb, cccc, ddd, { 1, 2, 3, 4, 5, 6, 777 })\n";
+    assert_eq!(
+        format_code(code, Config::default(), None, OutputVerification::None).unwrap(),
+        code
+    );
+}
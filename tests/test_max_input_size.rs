@@ -0,0 +1,49 @@
+use stylua_lib::{format_block_code, format_code, Config, Error, OutputVerification};
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_input_within_limit() {
+    assert_eq!(
+        format_code(
+            "local   x =  1",
+            Config::default().with_max_input_size(14),
+            None,
+            OutputVerification::None
+        )
+        .unwrap(),
+        "local x = 1\n"
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_input_too_large() {
+    assert!(matches!(
+        format_code(
+            "local   x =  1",
+            Config::default().with_max_input_size(13),
+            None,
+            OutputVerification::None
+        ),
+        Err(Error::InputTooLarge(14, 13))
+    ));
+    assert!(matches!(
+        format_block_code("local   x =  1", Config::default().with_max_input_size(13)),
+        Err(Error::InputTooLarge(14, 13))
+    ));
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_input_too_large_before_parsing() {
+    // The size limit is checked before parsing, so invalid code is not reported as a parse error
+    assert!(matches!(
+        format_code(
+            "local = = =",
+            Config::default().with_max_input_size(4),
+            None,
+            OutputVerification::None
+        ),
+        Err(Error::InputTooLarge(11, 4))
+    ));
+}
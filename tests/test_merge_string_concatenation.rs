@@ -0,0 +1,67 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_merge_string_concatenation(true),
+        None,
+        OutputVerification::Full,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_merge_literal_concatenation() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local a = "a" .. "\n" .. 'b"'
+local b = foo .. "a" .. "b"
+local c = ("a" .. "b") .. "c"
+            "###
+        ),
+        @r###"
+    local a = 'a\nb"'
+    local b = foo .. "ab"
+    local c = "abc"
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_unsafe_concatenation_not_merged() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local a = "\1" .. "2"
+local b = "a\z" .. "  b"
+local c = "a" .. [[b]]
+local d = "a" .. 1
+            "###
+        ),
+        @r###"
+    local a = "\1" .. "2"
+    local b = "a\z" .. "  b"
+    local c = "a" .. [[b]]
+    local d = "a" .. 1
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_merge_long_concatenation() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local message = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa" .. "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb" .. "cccccccccccccccccccccccccccccccccccccccc"
+            "###
+        ),
+        @r###"
+    local message =
+    	"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbcccccccccccccccccccccccccccccccccccccccc"
+    "###
+    );
+}
@@ -192,3 +192,89 @@ local still_got_em = my_function({
     "###
     );
 }
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_no_parens_long_string() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+foo("xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx")
+local value = foo("xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx")
+object:method("xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx")
+"###
+        ),
+        @r###"
+    foo "xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx"
+    local value =
+    	foo "xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx"
+    object:method "xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx"
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_no_parens_method_call() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+foo({ a = 1 })
+object:method({ a = 1 })
+foo("string")
+object:method("string")
+foo([[string]])
+object:method([[string]])
+foo("string"):chained({ x })
+object:method("string"):chained({ x })
+foo({ 1 }).x = 1
+object:method({ 1 }).x = 1
+"###
+        ),
+        @r###"
+    foo { a = 1 }
+    object:method { a = 1 }
+    foo "string"
+    object:method "string"
+    foo [[string]]
+    object:method [[string]]
+    foo("string"):chained { x }
+    object:method("string"):chained { x }
+    foo({ 1 }).x = 1
+    object:method({ 1 }).x = 1
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_no_parens_call_with_suffixes() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local a = require("x")
+local b = require "x"
+local c = require("x").y
+local d = require "x".y
+local e = require("x")[1]
+local f = require("x"):z()
+            "###
+        ),
+        @r###"
+    local a = require "x"
+    local b = require "x"
+    local c = require("x").y
+    local d = require("x").y
+    local e = require("x")[1]
+    local f = require("x"):z()
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_no_parens_call_with_suffixes_idempotent() {
+    let code = "local a = require \"x\"\nlocal b = require(\"x\").y\nlocal c = require(\"x\")[1]\n";
+    assert_eq!(format(code), code);
+    assert_eq!(format(&format(code)), code);
+}
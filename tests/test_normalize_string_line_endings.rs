@@ -0,0 +1,43 @@
+use stylua_lib::{format_code, Config, LineEndings, OutputVerification};
+
+fn format(input: &str, line_endings: LineEndings, normalize_string_line_endings: bool) -> String {
+    format_code(
+        input,
+        Config::default()
+            .with_line_endings(line_endings)
+            .with_normalize_string_line_endings(normalize_string_line_endings),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+const MIXED_STRING: &str = "local text = [[\nfirst\r\nsecond\nthird\r\n]]\n";
+
+#[test]
+fn test_preserves_string_line_endings_by_default() {
+    assert_eq!(
+        format(MIXED_STRING, LineEndings::Windows, false),
+        "local text = [[\nfirst\r\nsecond\nthird\r\n]]\r\n"
+    );
+    assert_eq!(
+        format(MIXED_STRING, LineEndings::Unix, false),
+        "local text = [[\nfirst\r\nsecond\nthird\r\n]]\n"
+    );
+}
+
+#[test]
+fn test_normalize_string_line_endings_windows() {
+    assert_eq!(
+        format(MIXED_STRING, LineEndings::Windows, true),
+        "local text = [[\r\nfirst\r\nsecond\r\nthird\r\n]]\r\n"
+    );
+}
+
+#[test]
+fn test_normalize_string_line_endings_unix() {
+    assert_eq!(
+        format(MIXED_STRING, LineEndings::Unix, true),
+        "local text = [[\nfirst\nsecond\nthird\n]]\n"
+    );
+}
@@ -0,0 +1,83 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str, config: Config) -> String {
+    format_code(input, config, None, OutputVerification::Full).unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_preserve_number_notation() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local a = 1.0
+local b = .5
+local c = 5.
+local d = 1e3
+local e = 1E+3
+local f = 0X1F
+local g = 0xABCDEF
+local h = -2.50E-05
+            "###,
+            Config::default()
+        ),
+        @r###"
+    local a = 1.0
+    local b = 0.5
+    local c = 5.
+    local d = 1e3
+    local e = 1e+3
+    local f = 0x1F
+    local g = 0xABCDEF
+    local h = -2.50e-05
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_normalise_number_notation() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local a = 1.0
+local b = .5
+local c = 5.
+local d = 1E+3
+local e = 5.e+10
+            "###,
+            Config::default().with_preserve_number_notation(false)
+        ),
+        @r###"
+    local a = 1.0
+    local b = 0.5
+    local c = 5.0
+    local d = 1e3
+    local e = 5.0e10
+    "###
+    );
+}
+
+#[test]
+#[cfg(feature = "luau")]
+fn test_normalise_number_notation_separators() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local a = 1_000
+local b = 0X_FF
+local c = 1_000.5
+local d = 1_000.
+local e = 1_000E+1_0
+            "###,
+            Config::default().with_preserve_number_notation(false)
+        ),
+        @r###"
+    local a = 1_000
+    local b = 0x_FF
+    local c = 1_000.5
+    local d = 1_000.0
+    local e = 1_000e1_0
+    "###
+    );
+}
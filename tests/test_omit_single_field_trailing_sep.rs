@@ -0,0 +1,100 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_omit_single_field_trailing_sep(true),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_single_field_function() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local handlers = { onPlayerAddedToTheGameWithAVeryLongName = function(player) print(player.Name, "joined the game") end }
+"###
+        ),
+        @r###"
+    local handlers = {
+    	onPlayerAddedToTheGameWithAVeryLongName = function(player)
+    		print(player.Name, "joined the game")
+    	end
+    }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_single_field_comments() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local a = {
+    foo = 1, -- comment
+}
+local b = {
+    foo = 1 -- comment
+}
+"###
+        ),
+        @r###"
+    local a = {
+    	foo = 1, -- comment
+    }
+    local b = {
+    	foo = 1 -- comment
+    }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_multiple_fields() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local handlers = { onPlayerAdded = function(player) print(player.Name) end, onPlayerRemoving = function() end }
+"###
+        ),
+        @r###"
+    local handlers = {
+    	onPlayerAdded = function(player)
+    		print(player.Name)
+    	end,
+    	onPlayerRemoving = function() end,
+    }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_whitespace_only() {
+    insta::assert_snapshot!(
+        format_code(
+            r###"
+local handlers = { onPlayerAddedToTheGameWithAVeryLongName = function(player) print(player.Name, "joined the game") end, }
+"###,
+            Config::default()
+                .with_omit_single_field_trailing_sep(true)
+                .with_whitespace_only(true),
+            None,
+            OutputVerification::None,
+        )
+        .unwrap(),
+        @r###"
+    local handlers = {
+    	onPlayerAddedToTheGameWithAVeryLongName = function(player)
+    		print(player.Name, "joined the game")
+    	end,
+    }
+    "###
+    );
+}
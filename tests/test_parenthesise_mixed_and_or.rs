@@ -0,0 +1,75 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_parenthesise_mixed_and_or(true),
+        None,
+        OutputVerification::Full,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_parenthesise_mixed_and_or() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local a = x and y or z
+local b = x or y and z
+local c = a and b or c and d or e
+local d = x and y and z or w
+local e = not x and y or z
+            "###
+        ),
+        @r###"
+    local a = (x and y) or z
+    local b = x or (y and z)
+    local c = (a and b) or (c and d) or e
+    local d = (x and y and z) or w
+    local e = (not x and y) or z
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_existing_parentheses_unchanged() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local a = (x and y) or z
+local b = (x or y) and z
+local c = x and (y or z)
+local d = x or y or z
+            "###
+        ),
+        @r###"
+    local a = (x and y) or z
+    local b = (x or y) and z
+    local c = x and (y or z)
+    local d = x or y or z
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_parenthesise_hanging_mixed_and_or() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local value = x == 1 and "one" or x == 2 and "two" or x == 3 and "three" or x == 4 and "four" or x == 5 and "five" or "other"
+            "###
+        ),
+        @r###"
+    local value = (x == 1 and "one")
+    	or (x == 2 and "two")
+    	or (x == 3 and "three")
+    	or (x == 4 and "four")
+    	or (x == 5 and "five")
+    	or "other"
+    "###
+    );
+}
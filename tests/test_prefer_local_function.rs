@@ -0,0 +1,84 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_prefer_local_function(true),
+        None,
+        OutputVerification::Full,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_local_function() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+-- adds two numbers
+local add = function(a, b) -- trailing comment
+    return a + b
+end
+local noop = function(...) end
+"###
+        ),
+        @r###"
+    -- adds two numbers
+    local function add(a, b) -- trailing comment
+    	return a + b
+    end
+    local function noop(...) end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_recursive_reference() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local fib = function(n)
+    if n < 2 then return n end
+    return fib(n - 1) + fib(n - 2)
+end
+local callback = function() callback = nil end
+"###
+        ),
+        @r###"
+    local fib = function(n)
+    	if n < 2 then
+    		return n
+    	end
+    	return fib(n - 1) + fib(n - 2)
+    end
+    local callback = function()
+    	callback = nil
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_unsafe_assignments() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local a, b = function() end, function() end
+local c = function() end, 1
+local d = function() end or nil
+local e = -- comment
+    function() end
+"###
+        ),
+        @r###"
+    local a, b = function() end, function() end
+    local c = function() end, 1
+    local d = function() end or nil
+    local e = -- comment
+    	function() end
+    "###
+    );
+}
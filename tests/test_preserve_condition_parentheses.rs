@@ -0,0 +1,87 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str, preserve_condition_parentheses: bool) -> String {
+    format_code(
+        input,
+        Config::default().with_preserve_condition_parentheses(preserve_condition_parentheses),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_preserve_if_condition() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+if (a and b) then
+    print(a)
+elseif (c) then
+    print(c)
+end
+"###,
+            true
+        ),
+        @r###"
+    if (a and b) then
+    	print(a)
+    elseif c then
+    	print(c)
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_remove_if_condition() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+if (a and b) then
+    print(a)
+elseif (c) then
+    print(c)
+end
+"###,
+            false
+        ),
+        @r###"
+    if a and b then
+    	print(a)
+    elseif c then
+    	print(c)
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_preserve_loop_conditions() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+while (running) do
+    step()
+end
+
+repeat
+    step()
+until (done or failed)
+"###,
+            true
+        ),
+        @r###"
+    while running do
+    	step()
+    end
+
+    repeat
+    	step()
+    until (done or failed)
+    "###
+    );
+}
@@ -0,0 +1,95 @@
+use stylua_lib::{
+    format_block_code, format_code, format_code_with_reorder, format_code_with_sourcemap, Config,
+    OutputVerification, Range,
+};
+
+fn format(input: &str, range: Option<Range>) -> String {
+    format_code(
+        input,
+        Config::default().with_preserve_first_lines(3),
+        range,
+        OutputVerification::Full,
+    )
+    .unwrap()
+}
+
+const HEADER: &str = "--   This file was generated by   codegen.\n--   Do not edit  by hand.\nlocal   VERSION   =   \"1.0\"\n";
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_preserve_header() {
+    assert_eq!(
+        format(&format!("{}local   x =  1\nlocal y   = 2\n", HEADER), None),
+        format!("{}local x = 1\nlocal y = 2\n", HEADER)
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_preserve_header_only() {
+    assert_eq!(format(HEADER, None), HEADER);
+    assert_eq!(
+        format("--   header  \nlocal   x =  1", None),
+        "--   header  \nlocal   x =  1"
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_preserve_header_with_range() {
+    let code = format!("{}local   x =  1\nlocal   y =  2\n", HEADER);
+    let start = HEADER.len() + "local   x =  1\n".len();
+    assert_eq!(
+        format(&code, Some(Range::from_values(Some(start), None))),
+        format!("{}local   x =  1\nlocal y = 2\n", HEADER)
+    );
+}
+
+const SHORT_HEADER: &str = "-- gen   header\nlocal   VERSION   =   1\n";
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_preserve_header_block_code() {
+    assert_eq!(
+        format_block_code(
+            &format!("{}local    y=2", SHORT_HEADER),
+            Config::default().with_preserve_first_lines(2)
+        )
+        .unwrap(),
+        format!("{}local y = 2\n", SHORT_HEADER)
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_preserve_header_sourcemap() {
+    let code = format!("{}local    y=2", SHORT_HEADER);
+    let (output, mappings) = format_code_with_sourcemap(
+        &code,
+        Config::default().with_preserve_first_lines(2),
+        None,
+        OutputVerification::Full,
+    )
+    .unwrap();
+
+    assert_eq!(output, format!("{}local y = 2\n", SHORT_HEADER));
+    assert_eq!(mappings.len(), 1);
+    assert_eq!(&code[mappings[0].input.clone()], "local    y=2");
+    assert_eq!(&output[mappings[0].output.clone()], "local y = 2");
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_preserve_header_reorder() {
+    assert_eq!(
+        format_code_with_reorder(
+            &format!("{}local    y=2\nlocal    a=1", SHORT_HEADER),
+            Config::default().with_preserve_first_lines(2),
+            None,
+            OutputVerification::Full,
+            |a, b| a.cmp(b),
+        )
+        .unwrap(),
+        format!("{}local a = 1\nlocal y = 2\n", SHORT_HEADER)
+    );
+}
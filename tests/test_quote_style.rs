@@ -129,3 +129,69 @@ local i = 'foo"bar\'baz'
     "###
     );
 }
+
+const TABLE_STRING_KEYS: &str = r###"
+local t = {
+    ["with space"] = 1,
+    ['end'] = 2,
+    ["it's"] = 3,
+    [ 'a"b' ] = 4,
+}
+t['x y'] = t["function"]
+"###;
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_table_string_keys_auto_prefer_double_quotes() {
+    insta::assert_snapshot!(format(TABLE_STRING_KEYS, QuoteStyle::AutoPreferDouble), @r###"
+    local t = {
+    	["with space"] = 1,
+    	["end"] = 2,
+    	["it's"] = 3,
+    	['a"b'] = 4,
+    }
+    t["x y"] = t["function"]
+    "###);
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_table_string_keys_auto_prefer_single_quotes() {
+    insta::assert_snapshot!(format(TABLE_STRING_KEYS, QuoteStyle::AutoPreferSingle), @r###"
+    local t = {
+    	['with space'] = 1,
+    	['end'] = 2,
+    	["it's"] = 3,
+    	['a"b'] = 4,
+    }
+    t['x y'] = t['function']
+    "###);
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_table_string_keys_force_double_quotes() {
+    insta::assert_snapshot!(format(TABLE_STRING_KEYS, QuoteStyle::ForceDouble), @r###"
+    local t = {
+    	["with space"] = 1,
+    	["end"] = 2,
+    	["it's"] = 3,
+    	["a\"b"] = 4,
+    }
+    t["x y"] = t["function"]
+    "###);
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_table_string_keys_force_single_quotes() {
+    insta::assert_snapshot!(format(TABLE_STRING_KEYS, QuoteStyle::ForceSingle), @r###"
+    local t = {
+    	['with space'] = 1,
+    	['end'] = 2,
+    	['it\'s'] = 3,
+    	['a"b'] = 4,
+    }
+    t['x y'] = t['function']
+    "###);
+}
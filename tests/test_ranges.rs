@@ -186,3 +186,74 @@ end
     end
     "###);
 }
+
+#[test]
+#[cfg(feature = "luau")]
+fn test_nested_range_continue() {
+    insta::assert_snapshot!(
+        format(
+            r###"for i = 1, 10 do
+    for j   =   1, 10 do
+        if j == 2 then   continue   end
+        print( j )
+    end
+    if i == 1 then
+        continue
+    end
+end
+"###,
+            Range::from_values(Some(21), Some(108))
+        ),
+    @r###"
+    for i = 1, 10 do
+    	for j = 1, 10 do
+    		if j == 2 then
+    			continue
+    		end
+    		print(j)
+    	end
+        if i == 1 then
+            continue
+        end
+    end
+    "###);
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_nested_range_tab_width() {
+    // Each tab counts as `indent_width` columns, so the second call is over width when nested four blocks deep,
+    // both when formatting the whole file and when only formatting the range
+    let input = "if a then
+\tif b then
+\t\tif c then
+\t\t\tif d then
+\t\t\t\tlocal result = someFunction(argumentNumberOne, argumentNumberTwo, argumentNumberThree, argumentFour)
+\t\t\t\tlocal other = someFunction(argumentNumberOne, argumentNumberTwo, argumentNumberThree, argumentFourXXXXXX)
+\t\t\tend
+\t\tend
+\tend
+end
+";
+    let full = format_code(input, Config::default(), None, OutputVerification::None).unwrap();
+    let range = format(input, Range::from_values(Some(50), Some(261)));
+    assert_eq!(range, full);
+
+    insta::assert_snapshot!(range, @r###"
+    if a then
+    	if b then
+    		if c then
+    			if d then
+    				local result = someFunction(argumentNumberOne, argumentNumberTwo, argumentNumberThree, argumentFour)
+    				local other = someFunction(
+    					argumentNumberOne,
+    					argumentNumberTwo,
+    					argumentNumberThree,
+    					argumentFourXXXXXX
+    				)
+    			end
+    		end
+    	end
+    end
+    "###);
+}
@@ -0,0 +1,82 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_reindent_multiline_strings(true),
+        None,
+        OutputVerification::Full,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_reindent_multiline_string() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local function foo()
+    local query = [[
+SELECT *
+    FROM users
+
+WHERE id = 1
+]]
+    return query
+end
+            "###
+        ),
+        @r###"
+    local function foo()
+    	local query = [[
+    	SELECT *
+    	    FROM users
+
+    	WHERE id = 1
+    	]]
+    	return query
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_reindent_multiline_string_first_line() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+if x then
+    print([==[first line
+                second line]==])
+end
+            "###
+        ),
+        @r###"
+    if x then
+    	print([==[first line
+    	second line]==])
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_single_line_string_unchanged() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+do
+    local x = [[  hello  ]]
+end
+            "###
+        ),
+        @r###"
+    do
+    	local x = [[  hello  ]]
+    end
+    "###
+    );
+}
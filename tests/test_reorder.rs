@@ -0,0 +1,63 @@
+use stylua_lib::{format_code_with_reorder, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    // Sort all `local x = require(...)` statements to the top, ordered by their code
+    format_code_with_reorder(
+        input,
+        Config::default(),
+        None,
+        OutputVerification::Full,
+        |a, b| {
+            let a_require = a.starts_with("local") && a.contains("require(");
+            let b_require = b.starts_with("local") && b.contains("require(");
+            match (a_require, b_require) {
+                (true, true) => a.cmp(b),
+                (true, false) => std::cmp::Ordering::Less,
+                (false, true) => std::cmp::Ordering::Greater,
+                (false, false) => std::cmp::Ordering::Equal,
+            }
+        },
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_reorder_statements() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local foo = require("foo")
+print("hello")
+-- bar is used for things
+local bar = require("bar") -- trailing comment
+print("world")
+return foo
+            "###
+        ),
+        @r###"
+    -- bar is used for things
+    local bar = require("bar") -- trailing comment
+
+    local foo = require("foo")
+    print("hello")
+    print("world")
+    return foo
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_identity_reorder() {
+    let input = "local b = 1\nlocal a = 2\n";
+    let output = format_code_with_reorder(
+        input,
+        Config::default(),
+        None,
+        OutputVerification::Full,
+        |_, _| std::cmp::Ordering::Equal,
+    )
+    .unwrap();
+    assert_eq!(output, input);
+}
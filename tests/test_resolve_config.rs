@@ -0,0 +1,35 @@
+use stylua_lib::{resolve_config, Config, IndentType, QuoteStyle};
+
+#[test]
+fn test_resolve_default_config() {
+    let config = resolve_config(Config::default());
+    assert_eq!(format!("{:?}", config), format!("{:?}", Config::default()));
+}
+
+#[test]
+fn test_resolve_provided_config() {
+    let provided = Config::default()
+        .with_indent_type(IndentType::Spaces)
+        .with_quote_style(QuoteStyle::ForceSingle)
+        .with_column_width(80);
+    let config = resolve_config(provided);
+
+    assert_eq!(config.indent_type(), IndentType::Spaces);
+    assert_eq!(config.column_width(), 80);
+    assert_eq!(format!("{:?}", config), format!("{:?}", provided));
+}
+
+#[test]
+fn test_resolve_whitespace_only_config() {
+    let provided = Config::default()
+        .with_whitespace_only(true)
+        .with_merge_string_concatenation(true)
+        .with_omit_single_field_trailing_sep(true)
+        .with_column_width(80);
+    let config = resolve_config(provided);
+
+    assert!(config.whitespace_only());
+    assert!(!config.merge_string_concatenation());
+    assert!(!config.omit_single_field_trailing_sep());
+    assert_eq!(config.column_width(), 80);
+}
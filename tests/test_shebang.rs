@@ -0,0 +1,64 @@
+use stylua_lib::{format_code, Config, LineEndings, OutputVerification, Range};
+
+fn format(input: &str, config: Config, range: Option<Range>) -> String {
+    format_code(input, config, range, OutputVerification::Full).unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_shebang() {
+    assert_eq!(
+        format(
+            "#!/usr/bin/env lua\n\n\nlocal   x =  1\n",
+            Config::default(),
+            None
+        ),
+        "#!/usr/bin/env lua\nlocal x = 1\n"
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_shebang_only() {
+    assert_eq!(
+        format("#!/usr/bin/env lua", Config::default(), None),
+        "#!/usr/bin/env lua\n"
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_shebang_line_endings() {
+    assert_eq!(
+        format(
+            "#!/usr/bin/env lua\nlocal   x =  1\n",
+            Config::default().with_line_endings(LineEndings::Windows),
+            None
+        ),
+        "#!/usr/bin/env lua\r\nlocal x = 1\r\n"
+    );
+    assert_eq!(
+        format(
+            "#!/usr/bin/env lua\r\nlocal   x =  1\r\n",
+            Config::default(),
+            None
+        ),
+        "#!/usr/bin/env lua\nlocal x = 1\n"
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_shebang_range() {
+    // The range includes the shebang line, so only the second statement is formatted
+    let input = "#!/usr/bin/env lua\nlocal   x =  1\nlocal   y =  2\n";
+    let start = input.find("local   y").unwrap();
+    assert_eq!(
+        format(
+            input,
+            Config::default(),
+            Some(Range::from_values(Some(start), None))
+        ),
+        "#!/usr/bin/env lua\nlocal   x =  1\nlocal y = 2\n"
+    );
+}
@@ -0,0 +1,60 @@
+use stylua_lib::{format_code_with_sourcemap, Config, OutputVerification};
+
+/// Formats the code, then lists each mapped statement as `input => output`, with newlines escaped
+fn format(input: &str) -> String {
+    let (output, mappings) =
+        format_code_with_sourcemap(input, Config::default(), None, OutputVerification::Full)
+            .unwrap();
+
+    mappings
+        .into_iter()
+        .map(|mapping| {
+            format!(
+                "{:?} => {:?}",
+                &input[mapping.input], &output[mapping.output]
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_sourcemap() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+-- comments are not included in the mapped range
+local   x   =   1 ;
+if x then print( 'hello' ) end
+local function foo(a,b) return a+b end
+            "###
+        ),
+        @r###"
+    "local   x   =   1" => "local x = 1"
+    "if x then print( 'hello' ) end" => "if x then\n\tprint(\"hello\")\nend"
+    "print( 'hello' )" => "print(\"hello\")"
+    "local function foo(a,b) return a+b end" => "local function foo(a, b)\n\treturn a + b\nend"
+    "return a+b" => "return a + b"
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_sourcemap_range() {
+    let input = "local  x  =  1\nlocal  y  =  2\n";
+    let (output, mappings) = format_code_with_sourcemap(
+        input,
+        Config::default(),
+        Some(stylua_lib::Range::from_values(Some(15), None)),
+        OutputVerification::Full,
+    )
+    .unwrap();
+
+    assert_eq!(output, "local  x  =  1\nlocal y = 2\n");
+    assert_eq!(mappings.len(), 2);
+    assert_eq!(mappings[0].input, mappings[0].output);
+    assert_eq!(&output[mappings[1].output.to_owned()], "local y = 2");
+    assert_eq!(mappings[1].input, 15..29);
+}
@@ -0,0 +1,67 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str, space_after_anonymous_function: bool) -> String {
+    format_code(
+        input,
+        Config::default().with_space_after_anonymous_function(space_after_anonymous_function),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_no_space_after_anonymous_function() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+foo(function () return 1 end, function  () end)
+local tbl = { callback = function ()
+    print("hello")
+end, function () end }
+            "###,
+            false
+        ),
+        @r###"
+    foo(function()
+    	return 1
+    end, function() end)
+    local tbl = {
+    	callback = function()
+    		print("hello")
+    	end,
+    	function() end,
+    }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_space_after_anonymous_function() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+foo(function() return 1 end, function() end)
+local tbl = { callback = function()
+    print("hello")
+end, function() end }
+function bar() end
+            "###,
+            true
+        ),
+        @r###"
+    foo(function ()
+    	return 1
+    end, function () end)
+    local tbl = {
+    	callback = function ()
+    		print("hello")
+    	end,
+    	function () end,
+    }
+    function bar() end
+    "###
+    );
+}
@@ -0,0 +1,46 @@
+// Type assertions are only available in Luau
+#![cfg(feature = "luau")]
+
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str, spaces_around_type_assertion: bool) -> String {
+    format_code(
+        input,
+        Config::default().with_spaces_around_type_assertion(spaces_around_type_assertion),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+const TYPE_ASSERTIONS: &str = r###"
+local object = instance:FindFirstChild("Humanoid")::Humanoid
+local other = (someTable  ::  any).field :: number
+local value = getTheConfigurationValue(configurationKey, defaultConfigurationValue, fallbackConfigurationValue) :: ConfigurationValue
+"###;
+
+#[test]
+fn test_spaces_around_type_assertion() {
+    insta::assert_snapshot!(format(TYPE_ASSERTIONS, true), @r###"
+    local object = instance:FindFirstChild("Humanoid") :: Humanoid
+    local other = (someTable :: any).field :: number
+    local value = getTheConfigurationValue(
+    	configurationKey,
+    	defaultConfigurationValue,
+    	fallbackConfigurationValue
+    ) :: ConfigurationValue
+    "###);
+}
+
+#[test]
+fn test_no_spaces_around_type_assertion() {
+    insta::assert_snapshot!(format(TYPE_ASSERTIONS, false), @r###"
+    local object = instance:FindFirstChild("Humanoid")::Humanoid
+    local other = (someTable::any).field::number
+    local value = getTheConfigurationValue(
+    	configurationKey,
+    	defaultConfigurationValue,
+    	fallbackConfigurationValue
+    )::ConfigurationValue
+    "###);
+}
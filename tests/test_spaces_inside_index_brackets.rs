@@ -0,0 +1,57 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str, spaces_inside_index_brackets: bool) -> String {
+    format_code(
+        input,
+        Config::default().with_spaces_inside_index_brackets(spaces_inside_index_brackets),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_no_spaces_inside_index_brackets() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local x = t[ key ]
+t[ a ][b  ] = a[ b[ c[d] ] ]
+print(t[ [[key]] ], t[ "key" ]:method())
+local value = someTable[aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa + bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb] + anotherTable[ cccccccccccccccccccccccc ]
+            "###,
+            false
+        ),
+        @r###"
+    local x = t[key]
+    t[a][b] = a[b[c[d]]]
+    print(t[ [[key]] ], t["key"]:method())
+    local value = someTable[aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa + bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb]
+    	+ anotherTable[cccccccccccccccccccccccc]
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_spaces_inside_index_brackets() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local x = t[key]
+t[ a ][b  ] = a[ b[ c[d] ] ]
+print(t[ [[key]] ], t["key"]:method())
+local value = someTable[aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa + bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb] + anotherTable[ cccccccccccccccccccccccc ]
+            "###,
+            true
+        ),
+        @r###"
+    local x = t[ key ]
+    t[ a ][ b ] = a[ b[ c[ d ] ] ]
+    print(t[ [[key]] ], t[ "key" ]:method())
+    local value = someTable[ aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa + bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb ]
+    	+ anotherTable[ cccccccccccccccccccccccc ]
+    "###
+    );
+}
@@ -0,0 +1,49 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_spaces_inside_parentheses(true),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_nested_parentheses() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local x = (a + b) * ((c - d) / e)
+local y = (a or b).c
+foo((a + b) * c)
+            "###
+        ),
+        @r###"
+    local x = ( a + b ) * ( ( c - d ) / e )
+    local y = (a or b).c
+    foo(( a + b ) * c)
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_wrapped_parentheses() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local z = (aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa + bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb + cccccccccccccccccccccccccccccc) * dddddddddd
+            "###
+        ),
+        @r###"
+    local z = (
+    		aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa
+    		+ bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb
+    		+ cccccccccccccccccccccccccccccc
+    	) * dddddddddd
+    "###
+    );
+}
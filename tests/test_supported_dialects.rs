@@ -0,0 +1,21 @@
+use stylua_lib::supported_dialects;
+
+#[test]
+fn test_supported_dialects() {
+    let dialects = supported_dialects();
+    assert_eq!(dialects.luau, cfg!(feature = "luau"));
+    assert_eq!(dialects.lua52, cfg!(feature = "lua52"));
+    assert_eq!(dialects.names()[0], "Lua 5.1");
+}
+
+#[test]
+#[cfg(all(feature = "luau", not(feature = "lua52")))]
+fn test_supported_dialects_luau() {
+    assert_eq!(supported_dialects().to_string(), "Lua 5.1, Luau");
+}
+
+#[test]
+#[cfg(not(any(feature = "luau", feature = "lua52")))]
+fn test_supported_dialects_default() {
+    assert_eq!(supported_dialects().to_string(), "Lua 5.1");
+}
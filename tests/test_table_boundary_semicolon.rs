@@ -0,0 +1,39 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_preserve_table_boundary_semicolon(true),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_preserve_boundary_semicolon() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local a = { 1, 2, 3; foo = "bar", baz = 1 }
+local b = {
+    1; 2; foo = 3; 4
+}
+local c = { foo = 1; 2 }
+local d = { 1, 2, foo = 3 }
+            "###
+        ),
+        @r###"
+    local a = { 1, 2, 3; foo = "bar", baz = 1 }
+    local b = {
+    	1,
+    	2;
+    	foo = 3,
+    	4,
+    }
+    local c = { foo = 1, 2 }
+    local d = { 1, 2, foo = 3 }
+    "###
+    );
+}
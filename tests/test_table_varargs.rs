@@ -0,0 +1,62 @@
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str, config: Config) -> String {
+    format_code(input, config, None, OutputVerification::None).unwrap()
+}
+
+const INPUT: &str = r###"
+local a = {...}
+local b = { a,... }
+local c = {a, b, ...,}
+local d = {
+    a, ...
+}
+"###;
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_varargs_default() {
+    insta::assert_snapshot!(format(INPUT, Config::default()), @r###"
+    local a = { ... }
+    local b = { a, ... }
+    local c = { a, b, ... }
+    local d = {
+    	a,
+    	...,
+    }
+    "###);
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_varargs_no_extra_spaces() {
+    insta::assert_snapshot!(
+        format(INPUT, Config::default().with_extra_spaces_inside_table(false)),
+        @r###"
+    local a = {...}
+    local b = {a, ...}
+    local c = {a, b, ...}
+    local d = {
+    	a,
+    	...,
+    }
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_varargs_extra_sep_at_table_end() {
+    insta::assert_snapshot!(
+        format(INPUT, Config::default().with_extra_sep_at_table_end(true)),
+        @r###"
+    local a = { ..., }
+    local b = { a, ..., }
+    local c = { a, b, ..., }
+    local d = {
+    	a,
+    	...,
+    }
+    "###
+    );
+}
@@ -0,0 +1,38 @@
+use full_moon::ast::{Expression, LastStmt};
+use stylua_lib::trivia::{contains_comments, printed_width, strip_trivia};
+
+fn parse_return_expression(code: &str) -> Expression {
+    let ast = full_moon::parse(code).unwrap();
+    match ast.nodes().last_stmt() {
+        Some(LastStmt::Return(ret)) => ret.returns().iter().next().unwrap().to_owned(),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_contains_comments() {
+    assert!(contains_comments(parse_return_expression(
+        "return foo --[[ comment ]] + bar"
+    )));
+    assert!(!contains_comments(parse_return_expression(
+        "return foo + bar"
+    )));
+}
+
+#[test]
+fn test_strip_trivia() {
+    let expression = parse_return_expression("return foo + bar -- comment\n");
+    let stripped = strip_trivia(&expression);
+    assert_eq!(stripped.to_string(), "foo + bar");
+    assert!(!contains_comments(stripped));
+}
+
+#[test]
+fn test_printed_width() {
+    let expression = parse_return_expression("return   foo + bar -- comment\n");
+    assert_eq!(printed_width(&expression), 9);
+
+    // Widths are counted in bytes
+    let expression = parse_return_expression("return \"é\"");
+    assert_eq!(printed_width(&expression), 4);
+}
@@ -0,0 +1,68 @@
+use std::convert::TryFrom;
+use stylua_lib::{format_code, Config, OutputVerification, VerbatimCommentPrefixes};
+
+fn prefixes(prefixes: &[&str]) -> VerbatimCommentPrefixes {
+    VerbatimCommentPrefixes::try_from(
+        prefixes
+            .iter()
+            .map(|prefix| prefix.to_string())
+            .collect::<Vec<_>>(),
+    )
+    .unwrap()
+}
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_verbatim_comment_prefixes(prefixes(&["--!", "---@", "--#region"])),
+        None,
+        OutputVerification::None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_verbatim_comments() {
+    assert_eq!(
+        format("--!strict  \n---@class Foo\t\nlocal   x = 1 --#region  Foo  \n-- not verbatim  \n"),
+        "--!strict  \n---@class Foo\t\nlocal x = 1 --#region  Foo  \n-- not verbatim\n"
+    );
+}
+
+#[test]
+fn test_no_verbatim_comments_by_default() {
+    assert_eq!(
+        format_code(
+            "--!strict  \nlocal x = 1\n",
+            Config::default(),
+            None,
+            OutputVerification::None
+        )
+        .unwrap(),
+        "--!strict\nlocal x = 1\n"
+    );
+}
+
+#[test]
+fn test_prefixes_from_toml() {
+    let config: Config = toml::from_str("verbatim_comment_prefixes = [\"--!\", \"---@\"]").unwrap();
+    assert_eq!(
+        config.verbatim_comment_prefixes().to_vec(),
+        vec!["--!".to_owned(), "---@".to_owned()]
+    );
+    assert!(config
+        .verbatim_comment_prefixes()
+        .matches("---@param x number"));
+    assert!(!config
+        .verbatim_comment_prefixes()
+        .matches("-- @param x number"));
+}
+
+#[test]
+fn test_prefixes_capacity() {
+    let too_many = vec!["--!".to_owned(); VerbatimCommentPrefixes::MAX_PREFIXES + 1];
+    assert!(VerbatimCommentPrefixes::try_from(too_many).is_err());
+
+    let too_long = vec!["-".repeat(VerbatimCommentPrefixes::MAX_PREFIX_LEN + 1)];
+    assert!(VerbatimCommentPrefixes::try_from(too_long).is_err());
+}
@@ -0,0 +1,181 @@
+use stylua_lib::{format_code, AstVerifier, Config, Mismatch, OutputVerification};
+
+fn compare(input: &str, output: &str) -> Result<(), Mismatch> {
+    AstVerifier::new(Config::default()).compare_detailed(
+        full_moon::parse(input).unwrap(),
+        full_moon::parse(output).unwrap(),
+    )
+}
+
+#[test]
+fn test_compare_detailed_similar() {
+    assert_eq!(
+        compare(
+            "local x = ('foo');\nprint(x)",
+            "local x = \"foo\"\nprint(x)"
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_compare_detailed_token_mismatch() {
+    assert_eq!(
+        compare("local x = 1\nprint(x + 1)", "local x = 1\nprint(x - 1)"),
+        Err(Mismatch {
+            node_kind: "FunctionCall",
+            expected: Some("+".to_owned()),
+            actual: Some("-".to_owned()),
+        })
+    );
+}
+
+#[test]
+fn test_compare_detailed_missing_statement() {
+    assert_eq!(
+        compare("local x = 1\nprint(x)", "local x = 1"),
+        Err(Mismatch {
+            node_kind: "Block",
+            expected: Some("print(x)".to_owned()),
+            actual: None,
+        })
+    );
+}
+
+#[test]
+fn test_compare_detailed_last_stmt_mismatch() {
+    assert_eq!(
+        compare("return 1, 2", "return 1"),
+        Err(Mismatch {
+            node_kind: "Return",
+            expected: Some(",".to_owned()),
+            actual: None,
+        })
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_verify_ambiguous_syntax() {
+    for input in [
+        include_str!("inputs/ambiguous-syntax.lua"),
+        include_str!("inputs/ambiguous-syntax-2.lua"),
+    ] {
+        assert!(format_code(input, Config::default(), None, OutputVerification::Full).is_ok());
+    }
+}
+
+#[test]
+#[cfg(feature = "luau")]
+fn test_verify_ambiguous_syntax_compound_assignment() {
+    let input = include_str!("inputs-luau/ambiguous-syntax-compound-assignment.lua");
+    assert!(format_code(input, Config::default(), None, OutputVerification::Full).is_ok());
+}
+
+#[test]
+fn test_verify_condition_parentheses_removal() {
+    let input = r#"
+if (x) then
+    print(x)
+elseif ((y)) then
+    print(y)
+end
+while (a and b) do end
+repeat until (not c)
+"#;
+    let output = format_code(input, Config::default(), None, OutputVerification::Full).unwrap();
+    assert_eq!(
+        output,
+        "if x then\n\tprint(x)\nelseif y then\n\tprint(y)\nend\nwhile a and b do\nend\nrepeat\nuntil not c\n"
+    );
+}
+
+#[test]
+fn test_compare_detailed_condition_parentheses() {
+    assert_eq!(
+        compare(
+            "if (x) then end\nwhile ((a and b)) do end",
+            "if x then end\nwhile a and b do end"
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_verify_normalised_string_line_endings() {
+    let input = "local text = [[\r\nfirst\r\nsecond\n]]\n";
+    let config = Config::default().with_normalize_string_line_endings(true);
+    assert!(format_code(input, config, None, OutputVerification::Full).is_ok());
+}
+
+#[test]
+fn test_compare_detailed_reindented_string() {
+    let input = full_moon::parse("local text = [[\n    first\n]]").unwrap();
+    let output = full_moon::parse("local text = [[\n\tfirst\n]]").unwrap();
+
+    assert!(AstVerifier::new(Config::default())
+        .compare_detailed(input.to_owned(), output.to_owned())
+        .is_err());
+    assert_eq!(
+        AstVerifier::new(Config::default().with_reindent_multiline_strings(true))
+            .compare_detailed(input, output),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_compare_detailed_string_line_endings() {
+    let input = full_moon::parse("local text = [[\r\nfirst\r\n]]").unwrap();
+    let output = full_moon::parse("local text = [[\nfirst\n]]").unwrap();
+
+    assert!(AstVerifier::new(Config::default())
+        .compare_detailed(input.to_owned(), output.to_owned())
+        .is_err());
+    assert_eq!(
+        AstVerifier::new(Config::default().with_normalize_string_line_endings(true))
+            .compare_detailed(input, output),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_compare_detailed_string_mismatch() {
+    assert_eq!(
+        compare("local x = 'foo'", "local x = \"bar\""),
+        Err(Mismatch {
+            node_kind: "LocalAssignment",
+            expected: Some("\"foo\"".to_owned()),
+            actual: Some("\"bar\"".to_owned()),
+        })
+    );
+}
+
+#[test]
+fn test_compare_detailed_local_function() {
+    let input = full_moon::parse("local f = function() end").unwrap();
+    let output = full_moon::parse("local function f() end").unwrap();
+
+    assert!(AstVerifier::new(Config::default())
+        .compare_detailed(input.to_owned(), output.to_owned())
+        .is_err());
+    assert_eq!(
+        AstVerifier::new(Config::default().with_prefer_local_function(true))
+            .compare_detailed(input, output),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_compare_detailed_string_concatenation() {
+    let input = full_moon::parse("local x = 'a' .. 'b'").unwrap();
+    let output = full_moon::parse("local x = 'ab'").unwrap();
+
+    assert!(AstVerifier::new(Config::default())
+        .compare_detailed(input.to_owned(), output.to_owned())
+        .is_err());
+    assert_eq!(
+        AstVerifier::new(Config::default().with_merge_string_concatenation(true))
+            .compare_detailed(input, output),
+        Ok(())
+    );
+}
@@ -0,0 +1,156 @@
+use full_moon::tokenizer::{tokens, TokenType};
+use stylua_lib::{format_code, Config, OutputVerification};
+
+fn format(input: &str) -> String {
+    format_code(
+        input,
+        Config::default().with_whitespace_only(true),
+        None,
+        OutputVerification::Full,
+    )
+    .unwrap()
+}
+
+/// Returns every token in the code which is not whitespace or a comment
+fn code_tokens(code: &str) -> Vec<String> {
+    tokens(code)
+        .unwrap()
+        .iter()
+        .filter(|token| {
+            !matches!(
+                token.token_type(),
+                TokenType::Whitespace { .. }
+                    | TokenType::SingleLineComment { .. }
+                    | TokenType::MultiLineComment { .. }
+                    | TokenType::Eof
+            )
+        })
+        .map(|token| token.to_string())
+        .collect()
+}
+
+/// Returns the text of every comment in the code, sorted. Comments may be moved onto a different line when formatting,
+/// where they can end up joined together with another comment, but their text must not be changed or lost.
+fn comments(code: &str) -> Vec<String> {
+    let mut comments: Vec<String> = tokens(code)
+        .unwrap()
+        .iter()
+        .filter_map(|token| match token.token_type() {
+            TokenType::SingleLineComment { comment } => Some(comment.to_string()),
+            TokenType::MultiLineComment { .. } => Some(token.to_string()),
+            _ => None,
+        })
+        .flat_map(|comment| {
+            comment
+                .split("--")
+                .map(|part| part.trim().to_owned())
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    comments.sort();
+    comments
+}
+
+// Comments trailing a table key are currently lost regardless of configuration
+const KNOWN_COMMENT_LOSS: &[&str] = &["table-comments-2.lua"];
+
+fn assert_tokens_preserved(directory: &str) {
+    for entry in std::fs::read_dir(directory).unwrap() {
+        let path = entry.unwrap().path();
+        if KNOWN_COMMENT_LOSS.iter().any(|name| path.ends_with(name)) {
+            continue;
+        }
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let formatted = format(&contents);
+
+        assert_eq!(
+            comments(&contents),
+            comments(&formatted),
+            "comments changed when formatting {}",
+            path.display()
+        );
+
+        let expected = code_tokens(&contents);
+        let actual = code_tokens(&formatted);
+        let length = expected.len().max(actual.len());
+        if let Some(index) = (0..length).find(|&index| expected.get(index) != actual.get(index)) {
+            let start = index.saturating_sub(5);
+            panic!(
+                "tokens changed when formatting {}: expected {:?}, found {:?}",
+                path.display(),
+                &expected[start..(index + 5).min(expected.len())],
+                &actual[start..(index + 5).min(actual.len())],
+            );
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_whitespace_only_preserves_tokens() {
+    assert_tokens_preserved("tests/inputs");
+}
+
+#[test]
+#[cfg(feature = "luau")]
+fn test_whitespace_only_preserves_tokens_luau() {
+    assert_tokens_preserved("tests/inputs-luau");
+}
+
+#[test]
+#[cfg(feature = "lua52")]
+fn test_whitespace_only_preserves_tokens_lua52() {
+    assert_tokens_preserved("tests/inputs-lua52");
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_whitespace_only() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+local x = ( 1 + 2 ) ;  local y = 'hello'
+print "hi"
+local t = {1,2;3}
+if (x) then return .5 end
+            "###
+        ),
+        @r###"
+    local x = (1 + 2);
+    local y = 'hello'
+    print "hi"
+    local t = { 1, 2; 3 }
+    if (x) then
+    	return .5
+    end
+    "###
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_whitespace_only_semicolon_spacing() {
+    insta::assert_snapshot!(
+        format(
+            r###"
+a = 1 ;
+local b = 2
+;
+local c = 3 --[[ note ]] ;  local d = 4 ; -- comment
+do return d ; end
+return a   ;
+"###
+        ),
+        @r###"
+    a = 1;
+    local b = 2;
+    local c = 3; --[[ note ]]
+    local d = 4; -- comment
+    do
+    	return d;
+    end
+    return a;
+    "###
+    );
+}
@@ -30,3 +30,11 @@ fn test_lua52() {
         insta::assert_snapshot!(format(&contents));
     })
 }
+
+#[test]
+#[cfg_attr(feature = "luau", ignore)]
+fn test_unop_not_parentheses_idempotent() {
+    let contents = std::fs::read_to_string("tests/inputs/unop-not-parentheses.lua").unwrap();
+    let formatted = format(&contents);
+    assert_eq!(format(&formatted), formatted);
+}